@@ -0,0 +1,173 @@
+//! Readable rendering of styled text for snapshot tests.
+
+use crate::{Color, ScriptPosition, Style, is_sequence_start, skip_sequence};
+
+/// Returns the styled text with ANSI escape sequences replaced by readable markers, e.g.,
+/// `«bold red»error«/»`.
+///
+/// This makes snapshots of styled output stable and reviewable. Each change of the style by a
+/// Select Graphic Rendition (SGR) control sequence is replaced by `«/»` if the previous style is
+/// not the default style, followed by a description of the new style (e.g., `«bold red on
+/// white»`) if it is not the default style. Control sequences that do not change the style are
+/// removed, so equivalent ways to set a style produce the same result. Therefore, text that does
+/// not reset its style at the end lacks the final `«/»`. Other escape sequences are replaced by
+/// their characters in brackets, with ESC written as `ESC` (e.g., `«ESC[?25l»`).
+#[must_use]
+pub fn annotate_ansi(text: &str) -> String {
+    let mut annotated = String::with_capacity(text.len());
+    let mut style = Style::default();
+    let mut rest = text;
+    while let Some(pos) = rest.find(is_sequence_start) {
+        annotated.push_str(&rest[..pos]);
+        rest = &rest[pos..];
+        let after = skip_sequence(rest);
+        let sequence = &rest[..rest.len() - after.len()];
+        if let Some(parameters) = sequence
+            .strip_prefix("\x1b[")
+            .and_then(|sequence| sequence.strip_suffix('m'))
+        {
+            let new_style = style.with_sgr_parameters(parameters);
+            if new_style != style {
+                if style != Style::default() {
+                    annotated.push_str("«/»");
+                }
+                if new_style != Style::default() {
+                    annotated.push('«');
+                    annotated.push_str(&describe(new_style));
+                    annotated.push('»');
+                }
+                style = new_style;
+            }
+        } else {
+            annotated.push('«');
+            for ch in sequence.chars() {
+                if ch == '\x1b' {
+                    annotated.push_str("ESC");
+                } else {
+                    annotated.extend(ch.escape_default());
+                }
+            }
+            annotated.push('»');
+        }
+        rest = after;
+    }
+    annotated.push_str(rest);
+    annotated
+}
+
+/// Returns a description of the style, e.g., `"bold red on white"`.
+#[must_use]
+fn describe(style: Style) -> String {
+    let mut words = Vec::new();
+    if style.bold {
+        words.push("bold");
+    }
+    if style.dim {
+        words.push("dim");
+    }
+    if style.italic {
+        words.push("italic");
+    }
+    if style.underlined {
+        words.push("underlined");
+    }
+    if style.blinking {
+        words.push("blinking");
+    }
+    if style.reverse {
+        words.push("reverse");
+    }
+    if style.strikethrough {
+        words.push("strikethrough");
+    }
+    match style.script_position {
+        Some(ScriptPosition::Superscript) => words.push("superscript"),
+        Some(ScriptPosition::Subscript) => words.push("subscript"),
+        None => {}
+    }
+    let font;
+    if let Some(number) = style.font {
+        font = format!("font-{number}");
+        words.push(&font);
+    }
+    if style.foreground_color != Color::Default {
+        words.push(color_name(style.foreground_color));
+    }
+    if style.background_color != Color::Default {
+        words.push("on");
+        words.push(color_name(style.background_color));
+    }
+    words.join(" ")
+}
+
+/// Returns the name of the color, e.g., `"light-red"`.
+#[must_use]
+const fn color_name(color: Color) -> &'static str {
+    match color {
+        Color::Default => "default",
+        Color::Black => "black",
+        Color::Red => "red",
+        Color::Green => "green",
+        Color::Yellow => "yellow",
+        Color::Blue => "blue",
+        Color::Magena => "magenta",
+        Color::Cyan => "cyan",
+        Color::LightGray => "light-gray",
+        Color::DarkGray => "dark-gray",
+        Color::LightRed => "light-red",
+        Color::LightGreen => "light-green",
+        Color::LightYellow => "light-yellow",
+        Color::LightBlue => "light-blue",
+        Color::LightMagenta => "light-magenta",
+        Color::LightCyan => "light-cyan",
+        Color::White => "white",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{DiagnosticDisplay, DiagnosticLevel};
+
+    use super::*;
+
+    #[test]
+    fn annotate_plain() {
+        assert_eq!(annotate_ansi("foo\nbar"), "foo\nbar");
+    }
+
+    #[test]
+    fn annotate_styles() {
+        assert_eq!(
+            annotate_ansi("\x1b[1;31mfoo\x1b[0m \x1b[4;102mbar\x1b[24mbaz\x1b[49m"),
+            "«bold red»foo«/» «underlined on light-green»bar«/»«on light-green»baz«/»"
+        );
+        assert_eq!(annotate_ansi("\x1b[35mfoo"), "«magenta»foo");
+        assert_eq!(
+            annotate_ansi("\x1b[2;3;7;9mfoo"),
+            "«dim italic reverse strikethrough»foo"
+        );
+        assert_eq!(annotate_ansi("\x1b[13mfoo\x1b[10m"), "«font-3»foo«/»");
+        assert_eq!(annotate_ansi("\x1b[0m\x1b[mfoo"), "foo");
+    }
+
+    #[test]
+    fn annotate_other_sequences() {
+        assert_eq!(
+            annotate_ansi("a\x1b[?25lb\x1b]0;title\x07c"),
+            "a«ESC[?25l»b«ESC]0;title\\u{7}»c"
+        );
+    }
+
+    #[test]
+    fn annotate_rendered_output() {
+        let diagnostic = DiagnosticDisplay {
+            level: DiagnosticLevel::Error,
+            message: "oops",
+            width: 80,
+        };
+        assert_eq!(
+            annotate_ansi(&diagnostic.to_string()),
+            "«bold light-red»error:«/» oops"
+        );
+    }
+}
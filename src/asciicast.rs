@@ -0,0 +1,197 @@
+//! Recording of output in the asciicast format.
+
+use core::{fmt::Write as _, time::Duration};
+use std::{
+    io::{self, Write},
+    time::Instant,
+};
+
+use crate::utf8::Utf8Decoder;
+
+/// Adapter that records everything written to a stream, with timestamps, in the [asciicast v2]
+/// format, so that the session can be replayed with `asciinema play`.
+///
+/// The output is written to the stream unchanged, and each write is recorded as an output event in
+/// `cast`, e.g., a file, timed relative to the creation of the recorder. To record output without
+/// displaying it, use [`io::sink`] as the stream. A character that is split across multiple writes
+/// is recorded when its last byte is written, and invalid UTF-8 is recorded as U+FFFD.
+///
+/// Since output that has been written to the stream cannot be taken back, an error that occurs
+/// while recording it does not fail the write. Instead, it is returned by the next write or flush,
+/// before anything is written to the stream.
+///
+/// [asciicast v2]: https://docs.asciinema.org/manual/asciicast/v2/
+#[derive(Debug)]
+pub struct AsciicastRecorder<W, C>
+where
+    W: Write,
+    C: Write,
+{
+    /// The stream to which output is written.
+    stream: W,
+    /// The stream to which the recording is written.
+    cast: C,
+    /// The time at which the recording started.
+    start: Instant,
+    /// The decoder of the output, which holds back an incomplete UTF-8 sequence at the end of the
+    /// previous write.
+    decoder: Utf8Decoder,
+    /// The error that occurred while recording output, which has not been returned yet.
+    error: Option<io::Error>,
+}
+
+impl<W, C> AsciicastRecorder<W, C>
+where
+    W: Write,
+    C: Write,
+{
+    /// Creates an adapter that writes output to `stream` and records it in `cast`, and writes the
+    /// header of the recording, which specifies the terminal size in columns and rows.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing the header fails.
+    pub fn new(stream: W, mut cast: C, width: usize, height: usize) -> io::Result<Self> {
+        writeln!(
+            cast,
+            "{{\"version\": 2, \"width\": {width}, \"height\": {height}}}"
+        )?;
+        Ok(Self {
+            stream,
+            cast,
+            start: Instant::now(),
+            decoder: Utf8Decoder::new(),
+            error: None,
+        })
+    }
+
+    /// Returns a reference to the stream.
+    #[inline]
+    #[must_use]
+    pub const fn get_ref(&self) -> &W {
+        &self.stream
+    }
+
+    /// Returns a mutable reference to the stream.
+    ///
+    /// Output that is written directly to the stream is not recorded.
+    #[inline]
+    #[must_use]
+    pub const fn get_mut(&mut self) -> &mut W {
+        &mut self.stream
+    }
+
+    /// Returns the stream and the stream to which the recording is written.
+    #[inline]
+    #[must_use]
+    pub fn into_inner(self) -> (W, C) {
+        (self.stream, self.cast)
+    }
+
+    /// Records the output as an event at the specified time since the start of the recording.
+    fn record(&mut self, elapsed: Duration, output: &[u8]) -> io::Result<()> {
+        let text = self.decoder.decode(output);
+        if text.is_empty() {
+            return Ok(());
+        }
+        writeln!(
+            self.cast,
+            "[{:.6}, \"o\", \"{}\"]",
+            elapsed.as_secs_f64(),
+            json_escape(&text)
+        )
+    }
+}
+
+impl<W, C> Write for AsciicastRecorder<W, C>
+where
+    W: Write,
+    C: Write,
+{
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if let Some(error) = self.error.take() {
+            return Err(error);
+        }
+        let len = self.stream.write(buf)?;
+        // The output has reached the stream, so the write must succeed.
+        self.error = self.record(self.start.elapsed(), &buf[..len]).err();
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if let Some(error) = self.error.take() {
+            return Err(error);
+        }
+        self.stream.flush()?;
+        self.cast.flush()
+    }
+}
+
+/// Returns the text escaped for use in a JSON string.
+#[must_use]
+fn json_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            '\0'..='\x1f' | '\x7f' => _ = write!(escaped, "\\u{:04x}", u32::from(ch)),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn asciicast_header_and_events() {
+        let mut recorder =
+            AsciicastRecorder::new(Vec::new(), Vec::new(), 80, 24).expect("writing succeeds");
+        recorder
+            .record(Duration::from_millis(1500), b"\x1b[1m\"hi\"\x1b[0m\r\n")
+            .expect("writing succeeds");
+        recorder
+            .record(Duration::from_secs(2), b"gr\xc3")
+            .expect("writing succeeds");
+        recorder
+            .record(Duration::from_millis(2250), b"\xbcn\xff\\")
+            .expect("writing succeeds");
+        let (_, cast) = recorder.into_inner();
+        assert_eq!(
+            String::from_utf8_lossy(&cast),
+            "{\"version\": 2, \"width\": 80, \"height\": 24}\n\
+             [1.500000, \"o\", \"\\u001b[1m\\\"hi\\\"\\u001b[0m\\r\\n\"]\n\
+             [2.000000, \"o\", \"gr\"]\n\
+             [2.250000, \"o\", \"ün\u{fffd}\\\\\"]\n"
+        );
+    }
+
+    #[test]
+    fn asciicast_passes_output_through() {
+        let mut recorder =
+            AsciicastRecorder::new(Vec::new(), Vec::new(), 80, 24).expect("writing succeeds");
+        recorder.write_all(b"foo").expect("writing succeeds");
+        let (output, cast) = recorder.into_inner();
+        assert_eq!(output, b"foo");
+        assert!(String::from_utf8_lossy(&cast).ends_with(", \"o\", \"foo\"]\n"));
+    }
+
+    #[test]
+    fn asciicast_recording_error() {
+        let mut cast = [0; 50];
+        let mut recorder =
+            AsciicastRecorder::new(Vec::new(), &mut cast[..], 80, 24).expect("writing succeeds");
+        assert_eq!(recorder.write(b"foo").expect("writing succeeds"), 3);
+        let error = recorder.write(b"bar").expect_err("recording fails");
+        assert_eq!(error.kind(), io::ErrorKind::WriteZero);
+        recorder.flush().expect("flushing succeeds");
+        let (output, _) = recorder.into_inner();
+        assert_eq!(output, b"foo");
+    }
+}
@@ -0,0 +1,166 @@
+//! Horizontal bar charts.
+
+use core::fmt::{self, Display, Formatter};
+
+use crate::{GlyphSet, Style, StyledDisplay, width::display_width};
+
+/// Labeled values displayed as horizontal bars.
+///
+/// When `BarChartDisplay` is formatted or converted to a string, each entry is displayed on a line
+/// consisting of its label, padded to the width of the widest label, a space, and a bar. The bars
+/// are scaled so that the bar of the greatest value fills the rest of the line up to
+/// [`width`](Self::width) columns. The bars are drawn with the bar segments of the
+/// [`glyphs`](Self::glyphs), so their resolution is one eighth of a column with
+/// [`GlyphSet::UNICODE`] and a full column with [`GlyphSet::ASCII`]. Negative values and NaN are
+/// displayed as empty bars. Labels are displayed in their styles, and bars in
+/// [`bar_style`](Self::bar_style). The lines are separated by line feeds, and the last line is not
+/// terminated.
+///
+/// ```text
+/// src    ████████████████████▌
+/// target ██████████████████████████████████████
+/// docs   ▊
+/// ```
+#[expect(clippy::exhaustive_structs)]
+pub struct BarChartDisplay<'a> {
+    /// The labels and values of the bars, from top to bottom.
+    pub entries: &'a [(StyledDisplay<&'a str>, f64)],
+    /// The style of the bars.
+    pub bar_style: Style,
+    /// The glyphs of which the bars consist.
+    pub glyphs: &'a GlyphSet,
+    /// The maximum number of columns occupied by a line.
+    pub width: usize,
+}
+
+impl Display for BarChartDisplay<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let label_width = self
+            .entries
+            .iter()
+            .map(|(label, _)| display_width(label.value))
+            .max()
+            .unwrap_or(0);
+        let bar_width = self.width.saturating_sub(label_width + 1);
+        let max_value = self
+            .entries
+            .iter()
+            .map(|&(_, value)| value)
+            .fold(0.0, f64::max);
+
+        // Number of steps into which a column is divided.
+        let steps = self.glyphs.bar_partial.len() + 1;
+
+        for (index, (label, value)) in self.entries.iter().enumerate() {
+            if index != 0 {
+                f.write_str("\n")?;
+            }
+            let length = if *value > 0.0 {
+                (value / max_value * (bar_width * steps) as f64).round() as usize
+            } else {
+                0
+            };
+            write!(f, "{label}")?;
+            if length != 0 {
+                let padding = label_width - display_width(label.value);
+                let mut bar = self.glyphs.bar_full.repeat(length / steps);
+                if let Some(partial) = self
+                    .glyphs
+                    .bar_partial
+                    .get((length % steps).wrapping_sub(1))
+                {
+                    bar.push_str(partial);
+                }
+                let styled = StyledDisplay {
+                    style: self.bar_style,
+                    value: bar,
+                };
+                write!(f, "{:padding$} {styled}", "")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Color;
+
+    use super::*;
+
+    /// Returns the text in the default style.
+    fn plain(value: &str) -> StyledDisplay<&str> {
+        StyledDisplay {
+            style: Style::default(),
+            value,
+        }
+    }
+
+    #[test]
+    fn bar_chart_empty() {
+        let chart = BarChartDisplay {
+            entries: &[],
+            bar_style: Style::default(),
+            glyphs: &GlyphSet::UNICODE,
+            width: 80,
+        };
+        assert_eq!(chart.to_string(), "");
+    }
+
+    #[test]
+    fn bar_chart_scaled() {
+        let chart = BarChartDisplay {
+            entries: &[
+                (plain("src"), 5.0),
+                (plain("target"), 8.0),
+                (plain("docs"), 0.3),
+                (plain("tmp"), -1.0),
+            ],
+            bar_style: Style::default(),
+            glyphs: &GlyphSet::UNICODE,
+            width: 11,
+        };
+        assert_eq!(chart.to_string(), "src    ██▌\ntarget ████\ndocs   ▏\ntmp");
+    }
+
+    #[test]
+    fn bar_chart_ascii() {
+        let chart = BarChartDisplay {
+            entries: &[(plain("a"), 3.0), (plain("b"), 1.0), (plain("c"), 0.1)],
+            bar_style: Style::default(),
+            glyphs: &GlyphSet::ASCII,
+            width: 8,
+        };
+        assert_eq!(
+            chart.to_string(),
+            "a ######
+b ##
+c"
+        );
+    }
+
+    #[test]
+    fn bar_chart_styled() {
+        let green = Style {
+            foreground_color: Color::Green,
+            ..Default::default()
+        };
+        let chart = BarChartDisplay {
+            entries: &[(
+                StyledDisplay {
+                    style: Style {
+                        bold: true,
+                        ..Default::default()
+                    },
+                    value: "a",
+                },
+                1.0,
+            )],
+            bar_style: green,
+            glyphs: &GlyphSet::UNICODE,
+            width: 4,
+        };
+        assert_eq!(chart.to_string(), "\x1b[1ma\x1b[0m \x1b[32m██\x1b[0m");
+    }
+}
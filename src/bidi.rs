@@ -0,0 +1,114 @@
+//! Handling of right-to-left text.
+
+use std::borrow::Cow;
+
+use crate::{is_sequence_start, skip_sequence};
+
+/// Right-to-left isolate (RLI), which starts a run of text that is laid out right-to-left
+/// independently of the surrounding text.
+const RIGHT_TO_LEFT_ISOLATE: char = '\u{2067}';
+
+/// Pop directional isolate (PDI), which ends a run of text started by an isolate.
+const POP_DIRECTIONAL_ISOLATE: char = '\u{2069}';
+
+/// Returns the text with each run of right-to-left text (e.g., Arabic or Hebrew) enclosed in
+/// Unicode directional isolates.
+///
+/// A run starts and ends with a right-to-left character and contains all characters in between that
+/// are not left-to-right letters, so that, e.g., spaces between right-to-left words stay within the
+/// run. Runs do not span line feeds, and ANSI escape sequences are passed through unchanged.
+///
+/// Terminals that implement the Unicode Bidirectional Algorithm then display such runs
+/// right-to-left without reordering the surrounding left-to-right text, so that, e.g., an Arabic
+/// file name in an English message is not scrambled. The isolates do not occupy any columns.
+/// Since truncation may remove the end of a run, truncate text before calling this function. The
+/// text is borrowed if it does not contain right-to-left characters.
+#[must_use]
+pub fn isolate_rtl(text: &str) -> Cow<'_, str> {
+    let mut isolated = String::new();
+    // End of the text that has been copied to `isolated`.
+    let mut copied = 0;
+    // Start of the current run and end of its last right-to-left character.
+    let mut run: Option<(usize, usize)> = None;
+    let mut index = 0;
+    while let Some(ch) = text[index..].chars().next() {
+        if is_sequence_start(ch) {
+            index = text.len() - skip_sequence(&text[index..]).len();
+            continue;
+        }
+        let end = index + ch.len_utf8();
+        if is_rtl(ch) {
+            run = Some((run.map_or(index, |(start, _)| start), end));
+        } else if (ch == '\n' || ch.is_alphabetic())
+            && let Some((start, run_end)) = run.take()
+        {
+            push_isolated(&mut isolated, &text[copied..start], &text[start..run_end]);
+            copied = run_end;
+        }
+        index = end;
+    }
+    if let Some((start, run_end)) = run {
+        push_isolated(&mut isolated, &text[copied..start], &text[start..run_end]);
+        copied = run_end;
+    }
+
+    if copied == 0 {
+        Cow::Borrowed(text)
+    } else {
+        isolated.push_str(&text[copied..]);
+        Cow::Owned(isolated)
+    }
+}
+
+/// Appends the preceding text and the run of right-to-left text, enclosed in directional isolates,
+/// to the string.
+fn push_isolated(isolated: &mut String, preceding: &str, run: &str) {
+    isolated.push_str(preceding);
+    isolated.push(RIGHT_TO_LEFT_ISOLATE);
+    isolated.push_str(run);
+    isolated.push(POP_DIRECTIONAL_ISOLATE);
+}
+
+/// Returns whether the character belongs to a right-to-left script.
+///
+/// This checks the Unicode blocks reserved for right-to-left scripts (e.g., Hebrew, Arabic, Syriac,
+/// and Thaana), including their presentation forms.
+#[inline]
+#[must_use]
+const fn is_rtl(ch: char) -> bool {
+    matches!(
+        ch,
+        '\u{590}'..='\u{8ff}'
+            | '\u{fb1d}'..='\u{fdff}'
+            | '\u{fe70}'..='\u{fefc}'
+            | '\u{10800}'..='\u{10fff}'
+            | '\u{1e800}'..='\u{1efff}'
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::width::display_width;
+
+    #[test]
+    fn isolate_ltr_only() {
+        assert!(matches!(isolate_rtl("foo bar"), Cow::Borrowed("foo bar")));
+        assert!(matches!(isolate_rtl("\u{feff}foo"), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn isolate_embedded_rtl() {
+        let result = isolate_rtl("copied שלום עולם.txt to backup/");
+        assert_eq!(result, "copied \u{2067}שלום עולם\u{2069}.txt to backup/");
+        assert_eq!(display_width(&result), 31);
+    }
+
+    #[test]
+    fn isolate_lines_and_escape_sequences() {
+        assert_eq!(
+            isolate_rtl("\x1b[1mمرحبا\x1b[0m\nسلام"),
+            "\x1b[1m\u{2067}مرحبا\u{2069}\x1b[0m\n\u{2067}سلام\u{2069}"
+        );
+    }
+}
@@ -0,0 +1,320 @@
+//! Styling of values depending on the values themselves.
+
+use core::fmt::Display;
+
+use crate::{Color, Style, StyledDisplay};
+
+/// Rule that selects a text style depending on a value.
+///
+/// The rule is implemented for closures that take a reference to a value and return a [`Style`],
+/// as well as for [`SignStyle`], [`ThresholdStyle`], and [`ColorScale`].
+pub trait StyleRule<T>
+where
+    T: ?Sized,
+{
+    /// Returns the style in which to display the value.
+    #[must_use]
+    fn style_for(&self, value: &T) -> Style;
+
+    /// Returns the value with the style selected by this rule.
+    #[inline]
+    #[must_use]
+    fn styled(&self, value: T) -> StyledDisplay<T>
+    where
+        T: Display + Sized,
+    {
+        StyledDisplay {
+            style: self.style_for(&value),
+            value,
+        }
+    }
+}
+
+impl<T, F> StyleRule<T> for F
+where
+    T: ?Sized,
+    F: Fn(&T) -> Style,
+{
+    #[inline]
+    fn style_for(&self, value: &T) -> Style {
+        self(value)
+    }
+}
+
+/// Rule that selects a text style depending on the sign of a value.
+///
+/// The rule applies to all types whose default value is zero, such as the primitive numeric types
+/// and [`Duration`](core::time::Duration).
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[expect(clippy::exhaustive_structs)]
+pub struct SignStyle {
+    /// The style of negative values.
+    pub negative: Style,
+    /// The style of values that are zero, or that cannot be compared to zero (e.g., NaN).
+    pub zero: Style,
+    /// The style of positive values.
+    pub positive: Style,
+}
+
+impl<T> StyleRule<T> for SignStyle
+where
+    T: Default + PartialOrd,
+{
+    fn style_for(&self, value: &T) -> Style {
+        let zero = T::default();
+        if *value < zero {
+            self.negative
+        } else if *value > zero {
+            self.positive
+        } else {
+            self.zero
+        }
+    }
+}
+
+/// Rule that selects a text style depending on whether a value reaches a threshold.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[expect(clippy::exhaustive_structs)]
+pub struct ThresholdStyle<T> {
+    /// The threshold.
+    pub threshold: T,
+    /// The style of values that are less than the threshold, or that cannot be compared to it.
+    pub below: Style,
+    /// The style of values that are greater than or equal to the threshold.
+    pub reached: Style,
+}
+
+impl<T> StyleRule<T> for ThresholdStyle<T>
+where
+    T: PartialOrd,
+{
+    fn style_for(&self, value: &T) -> Style {
+        if *value >= self.threshold {
+            self.reached
+        } else {
+            self.below
+        }
+    }
+}
+
+/// Rule that selects a foreground color from a gradient depending on the magnitude of a value.
+///
+/// The range from [`min`](Self::min) to [`max`](Self::max) is divided into as many equal intervals
+/// as there are [`colors`](Self::colors), and a value is displayed in the color of the interval it
+/// falls into. Values outside the range are displayed in the first or last color, and NaN is
+/// displayed in the default color. `min` may be greater than `max` to reverse the gradient.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[expect(clippy::exhaustive_structs)]
+pub struct ColorScale<'a> {
+    /// The value at the start of the gradient.
+    pub min: f64,
+    /// The value at the end of the gradient.
+    pub max: f64,
+    /// The colors of the gradient, from `min` to `max`.
+    pub colors: &'a [Color],
+}
+
+impl ColorScale<'static> {
+    /// Gradient from cold to hot colors, i.e., from blue over green to red.
+    pub const DEFAULT_COLORS: &'static [Color] = &[
+        Color::Blue,
+        Color::Cyan,
+        Color::Green,
+        Color::Yellow,
+        Color::Red,
+    ];
+
+    /// Gradient from blue to yellow, which can be distinguished by people with red-green color
+    /// vision deficiencies.
+    pub const COLORBLIND_SAFE_COLORS: &'static [Color] = &[
+        Color::Blue,
+        Color::LightBlue,
+        Color::LightGray,
+        Color::LightYellow,
+        Color::Yellow,
+    ];
+
+    /// Creates a color scale with the [default colors](Self::DEFAULT_COLORS).
+    #[inline]
+    #[must_use]
+    pub const fn new(min: f64, max: f64) -> Self {
+        Self {
+            min,
+            max,
+            colors: Self::DEFAULT_COLORS,
+        }
+    }
+
+    /// Creates a color scale with the [colorblind-safe colors](Self::COLORBLIND_SAFE_COLORS).
+    #[inline]
+    #[must_use]
+    pub const fn colorblind_safe(min: f64, max: f64) -> Self {
+        Self {
+            min,
+            max,
+            colors: Self::COLORBLIND_SAFE_COLORS,
+        }
+    }
+}
+
+impl ColorScale<'_> {
+    /// Returns the color of the value.
+    #[must_use]
+    pub fn color_for(&self, value: f64) -> Color {
+        let (Some(&first), Some(&last)) = (self.colors.first(), self.colors.last()) else {
+            return Color::Default;
+        };
+        if value.is_nan() {
+            return Color::Default;
+        }
+        let position = (value - self.min) / (self.max - self.min);
+        if position.is_nan() || position >= 1.0 {
+            // The position is NaN if the range is empty and the value is at its end.
+            last
+        } else if position <= 0.0 {
+            first
+        } else {
+            let count = self.colors.len();
+            self.colors[((position * count as f64) as usize).min(count - 1)]
+        }
+    }
+}
+
+impl<T> StyleRule<T> for ColorScale<'_>
+where
+    T: Copy + Into<f64>,
+{
+    fn style_for(&self, value: &T) -> Style {
+        Style {
+            foreground_color: self.color_for((*value).into()),
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+
+    use crate::Color;
+
+    use super::*;
+
+    /// Returns a style with red text.
+    fn red() -> Style {
+        Style {
+            foreground_color: Color::Red,
+            ..Default::default()
+        }
+    }
+
+    /// Returns a style with green text.
+    fn green() -> Style {
+        Style {
+            foreground_color: Color::Green,
+            ..Default::default()
+        }
+    }
+
+    /// Returns a style with bold text.
+    fn bold() -> Style {
+        Style {
+            bold: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn sign_style() {
+        let rule = SignStyle {
+            negative: red(),
+            zero: Style::default(),
+            positive: green(),
+        };
+        assert_eq!(rule.style_for(&-3), red());
+        assert_eq!(rule.style_for(&0), Style::default());
+        assert_eq!(rule.style_for(&7_u8), green());
+        assert_eq!(rule.style_for(&-0.5), red());
+        assert_eq!(rule.style_for(&f64::NAN), Style::default());
+    }
+
+    #[test]
+    fn sign_style_styled() {
+        let rule = SignStyle {
+            negative: red(),
+            zero: Style::default(),
+            positive: green(),
+        };
+        let result = format!("{:+.1}", rule.styled(2.25));
+        assert_eq!(result, "\x1b[32m+2.2\x1b[0m");
+    }
+
+    #[test]
+    fn threshold_style() {
+        let rule = ThresholdStyle {
+            threshold: Duration::from_secs(1),
+            below: Style::default(),
+            reached: bold(),
+        };
+        assert_eq!(
+            rule.style_for(&Duration::from_millis(999)),
+            Style::default()
+        );
+        assert_eq!(rule.style_for(&Duration::from_secs(1)), bold());
+        assert_eq!(rule.style_for(&Duration::from_secs(3)), bold());
+    }
+
+    #[test]
+    fn closure_rule() {
+        let rule = |value: &&str| {
+            if value.starts_with("error") {
+                red()
+            } else {
+                Style::default()
+            }
+        };
+        assert_eq!(rule.style_for(&"error: oops"), red());
+        assert_eq!(rule.styled("fine").to_string(), "fine");
+        assert_eq!(rule.styled("error").to_string(), "\x1b[31merror\x1b[0m");
+    }
+
+    #[test]
+    fn color_scale() {
+        let scale = ColorScale::new(0.0, 100.0);
+        assert_eq!(scale.color_for(-5.0), Color::Blue);
+        assert_eq!(scale.color_for(19.9), Color::Blue);
+        assert_eq!(scale.color_for(20.0), Color::Cyan);
+        assert_eq!(scale.color_for(50.0), Color::Green);
+        assert_eq!(scale.color_for(100.0), Color::Red);
+        assert_eq!(scale.color_for(1e9), Color::Red);
+        assert_eq!(scale.color_for(f64::NAN), Color::Default);
+    }
+
+    #[test]
+    fn color_scale_reversed_and_empty() {
+        let reversed = ColorScale::colorblind_safe(10.0, 0.0);
+        assert_eq!(reversed.color_for(10.0), Color::Blue);
+        assert_eq!(reversed.color_for(0.0), Color::Yellow);
+        let empty_range = ColorScale::new(5.0, 5.0);
+        assert_eq!(empty_range.color_for(5.0), Color::Red);
+        assert_eq!(empty_range.color_for(4.0), Color::Blue);
+        let no_colors = ColorScale {
+            colors: &[],
+            ..ColorScale::new(0.0, 1.0)
+        };
+        assert_eq!(no_colors.color_for(0.5), Color::Default);
+    }
+
+    #[test]
+    fn color_scale_styled() {
+        let scale = ColorScale::new(0.0, 10.0);
+        assert_eq!(scale.styled(7_u8).to_string(), "\x1b[33m7\x1b[0m");
+        assert_eq!(
+            scale.style_for(&0.5_f32),
+            Style {
+                foreground_color: Color::Blue,
+                ..Default::default()
+            }
+        );
+    }
+}
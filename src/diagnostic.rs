@@ -0,0 +1,184 @@
+//! Compiler-style diagnostic messages.
+
+use core::fmt::{self, Display, Formatter};
+
+use crate::{Color, Style, StyledDisplay, registry, width::display_width, wrap::wrap_line};
+
+/// Level of a diagnostic message.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum DiagnosticLevel {
+    /// An error.
+    Error,
+    /// A warning.
+    Warning,
+    /// A note providing additional information.
+    Note,
+    /// A hint on how to resolve a problem.
+    Hint,
+}
+
+impl DiagnosticLevel {
+    /// Returns the label of the level, e.g., `"error"`.
+    ///
+    /// The label is also the key under which the style of the level can be
+    /// [registered](registry::register) to override the default style.
+    #[inline]
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Error => "error",
+            Self::Warning => "warning",
+            Self::Note => "note",
+            Self::Hint => "hint",
+        }
+    }
+
+    /// Returns the style of the level's label.
+    ///
+    /// This is the style registered under the [label](Self::label) if there is one, and a bold,
+    /// level-specific color otherwise.
+    #[inline]
+    #[must_use]
+    pub fn style(self) -> Style {
+        self.style_from(registry::lookup)
+    }
+
+    /// Returns the style of the level's label, with registered styles looked up by the specified
+    /// function.
+    #[must_use]
+    fn style_from<F>(self, lookup: F) -> Style
+    where
+        F: FnOnce(&str) -> Option<Style>,
+    {
+        lookup(self.label()).unwrap_or_else(|| Style {
+            foreground_color: match self {
+                Self::Error => Color::LightRed,
+                Self::Warning => Color::LightYellow,
+                Self::Note => Color::LightGreen,
+                Self::Hint => Color::LightCyan,
+            },
+            bold: true,
+            ..Default::default()
+        })
+    }
+}
+
+/// Diagnostic message with a level prefix, e.g., `error: file not found`.
+///
+/// When `DiagnosticDisplay` is formatted or converted to a string, the label of the level and a
+/// colon are displayed in the level's [style](DiagnosticLevel::style), followed by a space and the
+/// message. The message is wrapped so that no line exceeds [`width`](Self::width) columns, and
+/// continuation lines, as well as further lines of messages that contain line feeds, are indented
+/// to align with the start of the message. The lines are separated by line feeds, and the last line
+/// is not terminated.
+#[derive(Clone, Copy, Debug)]
+#[expect(clippy::exhaustive_structs)]
+pub struct DiagnosticDisplay<'a> {
+    /// The level of the message.
+    pub level: DiagnosticLevel,
+    /// The message.
+    pub message: &'a str,
+    /// The maximum number of columns occupied by a line.
+    pub width: usize,
+}
+
+impl Display for DiagnosticDisplay<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let label = self.level.label();
+        let prefix = StyledDisplay {
+            style: self.level.style(),
+            value: format_args!("{label}:"),
+        };
+        write!(f, "{prefix}")?;
+
+        let indent = display_width(label) + 2;
+        let message_width = self.width.saturating_sub(indent);
+        let lines = self
+            .message
+            .split('\n')
+            .flat_map(|line| wrap_line(line, message_width));
+        for (index, line) in lines.enumerate() {
+            if index == 0 {
+                if !line.is_empty() {
+                    write!(f, " {line}")?;
+                }
+            } else if line.is_empty() {
+                f.write_str("\n")?;
+            } else {
+                write!(f, "\n{:indent$}{line}", "")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diagnostic_error() {
+        let diagnostic = DiagnosticDisplay {
+            level: DiagnosticLevel::Error,
+            message: "file not found",
+            width: 80,
+        };
+        assert_eq!(
+            diagnostic.to_string(),
+            "\x1b[91;1merror:\x1b[0m file not found"
+        );
+    }
+
+    #[test]
+    fn diagnostic_wrapped() {
+        let diagnostic = DiagnosticDisplay {
+            level: DiagnosticLevel::Note,
+            message: "the quick brown fox jumps\n\nover the lazy dog",
+            width: 21,
+        };
+        assert_eq!(
+            diagnostic.to_string(),
+            "\x1b[92;1mnote:\x1b[0m the quick brown\n      fox jumps\n\n      over the \
+             lazy\n      dog"
+        );
+    }
+
+    #[test]
+    fn diagnostic_levels() {
+        let labels = [
+            DiagnosticLevel::Error,
+            DiagnosticLevel::Warning,
+            DiagnosticLevel::Note,
+            DiagnosticLevel::Hint,
+        ]
+        .map(DiagnosticLevel::label);
+        assert_eq!(labels, ["error", "warning", "note", "hint"]);
+        assert_eq!(
+            DiagnosticLevel::Warning.style(),
+            Style {
+                foreground_color: Color::LightYellow,
+                bold: true,
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn diagnostic_registered_style() {
+        let style = Style {
+            foreground_color: Color::Blue,
+            ..Default::default()
+        };
+        let lookup = |key: &str| (key == "hint").then_some(style);
+        assert_eq!(DiagnosticLevel::Hint.style_from(lookup), style);
+        assert_eq!(
+            DiagnosticLevel::Note.style_from(lookup),
+            Style {
+                foreground_color: Color::LightGreen,
+                bold: true,
+                ..Default::default()
+            }
+        );
+    }
+}
@@ -2,7 +2,7 @@
 
 use core::fmt::{self, Display, Formatter};
 
-use crate::{RESET_STYLE, Style};
+use crate::{Color, RESET_STYLE, Style};
 
 /// Displayable value with associated text style information.
 ///
@@ -40,12 +40,209 @@ where
     }
 }
 
+/// Extension trait for styling values inline, e.g., `"error".red().bold()` or
+/// `count.styled(style)`.
+///
+/// The trait is implemented for string slices, strings, characters, Booleans, and numbers, which
+/// are converted to a [`StyledDisplay`] with the default style before the style is changed, and for
+/// `StyledDisplay` itself, whose style is changed, so that calls can be chained.
+pub trait StyleExt: Sized {
+    /// The type of the displayed value.
+    type Value: Display;
+
+    /// Returns the value as a [`StyledDisplay`].
+    #[must_use]
+    fn into_styled(self) -> StyledDisplay<Self::Value>;
+
+    /// Returns the value displayed in the specified style, which replaces the current style.
+    #[inline]
+    #[must_use]
+    fn styled(self, style: Style) -> StyledDisplay<Self::Value> {
+        let mut styled = self.into_styled();
+        styled.style = style;
+        styled
+    }
+
+    /// Returns the value displayed with the specified foreground color.
+    #[inline]
+    #[must_use]
+    fn fg(self, color: Color) -> StyledDisplay<Self::Value> {
+        let mut styled = self.into_styled();
+        styled.style = styled.style.fg(color);
+        styled
+    }
+
+    /// Returns the value displayed with the specified background color.
+    #[inline]
+    #[must_use]
+    fn bg(self, color: Color) -> StyledDisplay<Self::Value> {
+        let mut styled = self.into_styled();
+        styled.style = styled.style.bg(color);
+        styled
+    }
+
+    /// Returns the value displayed in black.
+    #[inline]
+    #[must_use]
+    fn black(self) -> StyledDisplay<Self::Value> {
+        self.fg(Color::Black)
+    }
+
+    /// Returns the value displayed in red.
+    #[inline]
+    #[must_use]
+    fn red(self) -> StyledDisplay<Self::Value> {
+        self.fg(Color::Red)
+    }
+
+    /// Returns the value displayed in green.
+    #[inline]
+    #[must_use]
+    fn green(self) -> StyledDisplay<Self::Value> {
+        self.fg(Color::Green)
+    }
+
+    /// Returns the value displayed in yellow.
+    #[inline]
+    #[must_use]
+    fn yellow(self) -> StyledDisplay<Self::Value> {
+        self.fg(Color::Yellow)
+    }
+
+    /// Returns the value displayed in blue.
+    #[inline]
+    #[must_use]
+    fn blue(self) -> StyledDisplay<Self::Value> {
+        self.fg(Color::Blue)
+    }
+
+    /// Returns the value displayed in magenta.
+    #[inline]
+    #[must_use]
+    fn magenta(self) -> StyledDisplay<Self::Value> {
+        self.fg(Color::Magena)
+    }
+
+    /// Returns the value displayed in cyan.
+    #[inline]
+    #[must_use]
+    fn cyan(self) -> StyledDisplay<Self::Value> {
+        self.fg(Color::Cyan)
+    }
+
+    /// Returns the value displayed in white.
+    #[inline]
+    #[must_use]
+    fn white(self) -> StyledDisplay<Self::Value> {
+        self.fg(Color::White)
+    }
+
+    /// Returns the value displayed in bold.
+    #[inline]
+    #[must_use]
+    fn bold(self) -> StyledDisplay<Self::Value> {
+        let mut styled = self.into_styled();
+        styled.style = styled.style.bold();
+        styled
+    }
+
+    /// Returns the value displayed dim (faint).
+    #[inline]
+    #[must_use]
+    fn dim(self) -> StyledDisplay<Self::Value> {
+        let mut styled = self.into_styled();
+        styled.style = styled.style.dim();
+        styled
+    }
+
+    /// Returns the value displayed in italics.
+    #[inline]
+    #[must_use]
+    fn italic(self) -> StyledDisplay<Self::Value> {
+        let mut styled = self.into_styled();
+        styled.style = styled.style.italic();
+        styled
+    }
+
+    /// Returns the value displayed underlined.
+    #[inline]
+    #[must_use]
+    fn underlined(self) -> StyledDisplay<Self::Value> {
+        let mut styled = self.into_styled();
+        styled.style = styled.style.underlined();
+        styled
+    }
+
+    /// Returns the value displayed struck through.
+    #[inline]
+    #[must_use]
+    fn strikethrough(self) -> StyledDisplay<Self::Value> {
+        let mut styled = self.into_styled();
+        styled.style = styled.style.strikethrough();
+        styled
+    }
+
+    /// Returns the value displayed with foreground and background colors swapped.
+    #[inline]
+    #[must_use]
+    fn reverse(self) -> StyledDisplay<Self::Value> {
+        let mut styled = self.into_styled();
+        styled.style = styled.style.reverse();
+        styled
+    }
+}
+
+impl<T> StyleExt for StyledDisplay<T>
+where
+    T: Display,
+{
+    type Value = T;
+
+    #[inline]
+    fn into_styled(self) -> Self {
+        self
+    }
+}
+
+impl<'a> StyleExt for &'a str {
+    type Value = &'a str;
+
+    #[inline]
+    fn into_styled(self) -> StyledDisplay<&'a str> {
+        StyledDisplay {
+            style: Style::new(),
+            value: self,
+        }
+    }
+}
+
+/// Implements [`StyleExt`] for types whose values are displayed as they are.
+macro_rules! impl_style_ext {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl StyleExt for $ty {
+                type Value = Self;
+
+                #[inline]
+                fn into_styled(self) -> StyledDisplay<Self> {
+                    StyledDisplay {
+                        style: Style::new(),
+                        value: self,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_style_ext!(
+    String, char, bool, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64,
+);
+
 #[cfg(test)]
 mod tests {
     use std::{fmt::Write as _, io::Write as _};
 
-    use crate::Color;
-
     use super::*;
 
     #[test]
@@ -126,4 +323,23 @@ mod tests {
         write!(&mut result, ">{styled:+.2}<").expect("writing to String failed");
         assert_eq!(result, ">\x1b[33m+17.50\x1b[0m<");
     }
+
+    #[test]
+    fn style_ext_chaining() {
+        assert_eq!("error".red().bold().to_string(), "\x1b[31;1merror\x1b[0m");
+        assert_eq!(
+            String::from("note").bg(Color::Blue).italic().to_string(),
+            "\x1b[44;3mnote\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn style_ext_styled_numbers() {
+        let style = Style::new().fg(Color::Green);
+        assert_eq!(42_u32.styled(style).to_string(), "\x1b[32m42\x1b[0m");
+        assert_eq!(
+            format!("{:.1}", 2.25_f64.styled(style).underlined()),
+            "\x1b[32;4m2.2\x1b[0m"
+        );
+    }
 }
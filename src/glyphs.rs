@@ -0,0 +1,151 @@
+//! Glyphs for borders, lists, trees, bars, and status icons, with ASCII fallbacks.
+
+use std::{env, ffi::OsString};
+
+/// Set of glyphs used by layout helpers, such as border, tree, and bar characters.
+///
+/// [`UNICODE`](Self::UNICODE) uses box-drawing and block characters, and [`ASCII`](Self::ASCII)
+/// uses only ASCII characters, for terminals that cannot display Unicode. [`detect`](Self::detect)
+/// selects one of them depending on the locale. Layout helpers that display glyphs take a glyph
+/// set, so that the choice can be overridden for each output stream.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[expect(clippy::exhaustive_structs)]
+pub struct GlyphSet {
+    /// Horizontal border line.
+    pub horizontal: &'static str,
+    /// Vertical border line.
+    pub vertical: &'static str,
+    /// Top left border corner.
+    pub top_left: &'static str,
+    /// Top right border corner.
+    pub top_right: &'static str,
+    /// Bottom left border corner.
+    pub bottom_left: &'static str,
+    /// Bottom right border corner.
+    pub bottom_right: &'static str,
+    /// Bullet of list items.
+    pub bullet: &'static str,
+    /// Ellipsis that replaces omitted text.
+    pub ellipsis: &'static str,
+    /// Tree guide before a node that has further siblings.
+    pub tree_branch: &'static str,
+    /// Tree guide before the last node among its siblings.
+    pub tree_last: &'static str,
+    /// Tree guide before the descendants of a node that has further siblings.
+    pub tree_continuation: &'static str,
+    /// Bar segment that fills a full column.
+    pub bar_full: &'static str,
+    /// Bar segments that fill fractions of a column, in ascending order of their fractions. If
+    /// there are n segments, the i-th segment fills i / (n + 1) of a column.
+    pub bar_partial: &'static [&'static str],
+    /// Icon of success messages.
+    pub check_mark: &'static str,
+    /// Icon of warning messages.
+    pub exclamation_mark: &'static str,
+    /// Icon of failure messages.
+    pub cross_mark: &'static str,
+}
+
+impl GlyphSet {
+    /// Glyphs consisting of ASCII characters.
+    pub const ASCII: Self = Self {
+        horizontal: "-",
+        vertical: "|",
+        top_left: "+",
+        top_right: "+",
+        bottom_left: "+",
+        bottom_right: "+",
+        bullet: "*",
+        ellipsis: "...",
+        tree_branch: "|-- ",
+        tree_last: "`-- ",
+        tree_continuation: "|   ",
+        bar_full: "#",
+        bar_partial: &[],
+        check_mark: "v",
+        exclamation_mark: "!",
+        cross_mark: "x",
+    };
+
+    /// Glyphs consisting of Unicode box-drawing and block characters.
+    pub const UNICODE: Self = Self {
+        horizontal: "─",
+        vertical: "│",
+        top_left: "┌",
+        top_right: "┐",
+        bottom_left: "└",
+        bottom_right: "┘",
+        bullet: "•",
+        ellipsis: "…",
+        tree_branch: "├── ",
+        tree_last: "└── ",
+        tree_continuation: "│   ",
+        bar_full: "█",
+        bar_partial: &["▏", "▎", "▍", "▌", "▋", "▊", "▉"],
+        check_mark: "✓",
+        exclamation_mark: "!",
+        cross_mark: "✗",
+    };
+
+    /// Returns [`UNICODE`](Self::UNICODE) if the locale's character encoding is UTF-8, as inferred
+    /// from environment variables, and [`ASCII`](Self::ASCII) otherwise.
+    ///
+    /// The first non-empty variable among `LC_ALL`, `LC_CTYPE`, and `LANG` determines the locale.
+    /// If none of them is set, Unicode is assumed on Windows and ASCII elsewhere.
+    #[must_use]
+    pub fn detect() -> &'static Self {
+        Self::detect_from(|name| env::var_os(name))
+    }
+
+    /// Returns the glyph set supported by the locale, as inferred from environment variables that
+    /// are looked up with the specified function.
+    fn detect_from<F>(var: F) -> &'static Self
+    where
+        F: Fn(&str) -> Option<OsString>,
+    {
+        let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+            .into_iter()
+            .filter_map(var)
+            .find(|value| !value.is_empty());
+        let unicode = locale.map_or(cfg!(windows), |locale| {
+            let locale = locale.to_string_lossy().to_ascii_lowercase();
+            locale.contains("utf-8") || locale.contains("utf8")
+        });
+        if unicode {
+            &Self::UNICODE
+        } else {
+            &Self::ASCII
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detect_glyph_set() {
+        let detect = |vars: &[(&str, &str)]| {
+            GlyphSet::detect_from(|name| {
+                vars.iter()
+                    .find(|(key, _)| *key == name)
+                    .map(|(_, value)| OsString::from(value))
+            })
+        };
+        assert_eq!(detect(&[("LANG", "en_US.UTF-8")]), &GlyphSet::UNICODE);
+        assert_eq!(detect(&[("LANG", "de_DE.utf8")]), &GlyphSet::UNICODE);
+        assert_eq!(detect(&[("LANG", "C")]), &GlyphSet::ASCII);
+        assert_eq!(
+            detect(&[
+                ("LC_ALL", ""),
+                ("LC_CTYPE", "POSIX"),
+                ("LANG", "en_US.UTF-8")
+            ]),
+            &GlyphSet::ASCII
+        );
+        assert_eq!(
+            detect(&[("LC_ALL", "C.UTF-8"), ("LANG", "C")]),
+            &GlyphSet::UNICODE
+        );
+    }
+}
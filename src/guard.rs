@@ -0,0 +1,104 @@
+//! Guards that finish the output written to a stream when they are dropped.
+
+use core::{mem::ManuallyDrop, ptr};
+use std::io;
+
+/// State of a [`FinishOnDrop`] guard, which determines how the output written to the stream is
+/// finished.
+pub(crate) trait Finish<W> {
+    /// Writes output that has been held back, or restores the state of the stream that has been
+    /// changed by the guard.
+    fn finish(&mut self, stream: &mut W) -> io::Result<()>;
+}
+
+/// Stream together with the state of a guard, which finishes the output written to the stream when
+/// the guard is dropped.
+///
+/// Errors that occur while finishing the output on drop are ignored; use
+/// [`into_stream`](Self::into_stream) to handle them.
+#[derive(Debug)]
+pub(crate) struct FinishOnDrop<W, S>
+where
+    S: Finish<W>,
+{
+    /// The stream to which output is written.
+    pub(crate) stream: W,
+    /// The state of the guard.
+    pub(crate) state: S,
+}
+
+impl<W, S> FinishOnDrop<W, S>
+where
+    S: Finish<W>,
+{
+    /// Creates a guard that finishes the output written to `stream` according to `state`.
+    #[inline]
+    #[must_use]
+    pub(crate) const fn new(stream: W, state: S) -> Self {
+        Self { stream, state }
+    }
+
+    /// Finishes the output and returns the stream.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if finishing the output fails.
+    pub(crate) fn into_stream(mut self) -> io::Result<W> {
+        self.state.finish(&mut self.stream)?;
+        let mut this = ManuallyDrop::new(self);
+        // SAFETY: `this` is never used or dropped again after the stream is moved out of it, and
+        // the state is dropped in place.
+        unsafe {
+            ptr::drop_in_place(&raw mut this.state);
+            Ok(ptr::read(&raw mut this.stream))
+        }
+    }
+}
+
+impl<W, S> Drop for FinishOnDrop<W, S>
+where
+    S: Finish<W>,
+{
+    fn drop(&mut self) {
+        // Errors cannot be reported from `drop`.
+        _ = self.state.finish(&mut self.stream);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::*;
+
+    /// State that appends a marker to the stream when the output is finished.
+    #[derive(Debug)]
+    struct Marker(&'static [u8]);
+
+    impl<W> Finish<W> for Marker
+    where
+        W: Write,
+    {
+        fn finish(&mut self, stream: &mut W) -> io::Result<()> {
+            stream.write_all(self.0)
+        }
+    }
+
+    #[test]
+    fn finish_into_stream() {
+        let mut guard = FinishOnDrop::new(Vec::new(), Marker(b"end"));
+        guard.stream.extend_from_slice(b"foo ");
+        let result = guard.into_stream().expect("writing to Vec failed");
+        assert_eq!(result, b"foo end");
+    }
+
+    #[test]
+    fn finish_on_drop() {
+        let mut result = Vec::new();
+        {
+            let guard = FinishOnDrop::new(&mut result, Marker(b"end"));
+            guard.stream.extend_from_slice(b"foo ");
+        }
+        assert_eq!(result, b"foo end");
+    }
+}
@@ -0,0 +1,193 @@
+//! Hexadecimal dumps of binary data.
+
+use core::{
+    fmt::{self, Display, Formatter},
+    ops::Range,
+};
+
+use crate::{Style, StyledDisplay};
+
+/// Number of bytes in a group of the hexadecimal pane. Groups are separated by an additional space.
+const GROUP_LEN: usize = 8;
+
+/// Minimum number of hexadecimal digits of an offset.
+const MIN_OFFSET_DIGITS: usize = 8;
+
+/// Binary data displayed as a hexadecimal dump, similar to `hexdump -C`.
+///
+/// When `HexdumpDisplay` is formatted or converted to a string, each row consists of the offset of
+/// its first byte, the bytes in hexadecimal in groups of eight, and the bytes as ASCII characters,
+/// with bytes that are not printable ASCII characters displayed as `.`. The number of bytes per row
+/// is the largest multiple of eight such that a row occupies at most [`width`](Self::width)
+/// columns, but at least eight. The rows are separated by line feeds, and the last row is not
+/// terminated.
+///
+/// ```text
+/// 00000000  48 65 6c 6c 6f 2c 20 77  6f 72 6c 64 21 0a 00 00  |Hello, world!...|
+/// ```
+#[derive(Clone, Copy, Debug)]
+#[expect(clippy::exhaustive_structs)]
+pub struct HexdumpDisplay<'a> {
+    /// The binary data.
+    pub bytes: &'a [u8],
+    /// The offset displayed for the first byte, e.g., the position of the data in a file.
+    pub start_offset: usize,
+    /// The styles of byte ranges, where the ranges are indices into [`bytes`](Self::bytes). If
+    /// ranges overlap, the first one applies.
+    pub highlights: &'a [(Range<usize>, Style)],
+    /// The style of NUL bytes that are not in a highlighted range.
+    pub nul_style: Style,
+    /// The style of the offsets.
+    pub offset_style: Style,
+    /// The maximum number of columns occupied by a row.
+    pub width: usize,
+}
+
+impl HexdumpDisplay<'_> {
+    /// Returns the style of the byte at the specified index.
+    #[must_use]
+    fn byte_style(&self, index: usize) -> Style {
+        self.highlights
+            .iter()
+            .find(|(range, _)| range.contains(&index))
+            .map_or_else(
+                || {
+                    if self.bytes[index] == 0 {
+                        self.nul_style
+                    } else {
+                        Style::default()
+                    }
+                },
+                |&(_, style)| style,
+            )
+    }
+}
+
+impl Display for HexdumpDisplay<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let last_offset = self.start_offset + self.bytes.len().saturating_sub(1);
+        let offset_digits =
+            (last_offset.checked_ilog(16).unwrap_or(0) as usize + 1).max(MIN_OFFSET_DIGITS);
+        // A row of n bytes occupies offset_digits + 4n + n / 8 + 4 columns.
+        let groups = (self.width.saturating_sub(offset_digits + 4) / (4 * GROUP_LEN + 1)).max(1);
+        let row_len = groups * GROUP_LEN;
+
+        for (row, chunk) in self.bytes.chunks(row_len).enumerate() {
+            let row_start = row * row_len;
+            if row != 0 {
+                f.write_str("\n")?;
+            }
+            let offset = StyledDisplay {
+                style: self.offset_style,
+                value: format_args!("{:0offset_digits$x}", self.start_offset + row_start),
+            };
+            write!(f, "{offset}")?;
+
+            for index in 0..row_len {
+                f.write_str(if index % GROUP_LEN == 0 { "  " } else { " " })?;
+                if let Some(&byte) = chunk.get(index) {
+                    let hex = StyledDisplay {
+                        style: self.byte_style(row_start + index),
+                        value: format_args!("{byte:02x}"),
+                    };
+                    write!(f, "{hex}")?;
+                } else {
+                    f.write_str("  ")?;
+                }
+            }
+
+            f.write_str("  |")?;
+            for (index, &byte) in chunk.iter().enumerate() {
+                let ch = if byte.is_ascii_graphic() || byte == b' ' {
+                    char::from(byte)
+                } else {
+                    '.'
+                };
+                let ascii = StyledDisplay {
+                    style: self.byte_style(row_start + index),
+                    value: ch,
+                };
+                write!(f, "{ascii}")?;
+            }
+            f.write_str("|")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Color;
+
+    use super::*;
+
+    /// Returns a hexdump of the bytes in the default styles.
+    fn hexdump(bytes: &[u8], width: usize) -> HexdumpDisplay<'_> {
+        HexdumpDisplay {
+            bytes,
+            start_offset: 0,
+            highlights: &[],
+            nul_style: Style::default(),
+            offset_style: Style::default(),
+            width,
+        }
+    }
+
+    #[test]
+    fn hexdump_empty() {
+        assert_eq!(hexdump(b"", 80).to_string(), "");
+    }
+
+    #[test]
+    fn hexdump_full_row() {
+        assert_eq!(
+            hexdump(b"Hello, world!\n\0\xff", 80).to_string(),
+            "00000000  48 65 6c 6c 6f 2c 20 77  6f 72 6c 64 21 0a 00 ff  |Hello, world!...|"
+        );
+    }
+
+    #[test]
+    fn hexdump_narrow() {
+        assert_eq!(
+            hexdump(b"0123456789", 60).to_string(),
+            "00000000  30 31 32 33 34 35 36 37  |01234567|\n\
+             00000008  38 39                    |89|"
+        );
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn hexdump_wide_offset() {
+        let dump = HexdumpDisplay {
+            start_offset: 0x1_0000_0000,
+            ..hexdump(b"a", 0)
+        };
+        assert_eq!(dump.to_string(), "100000000  61                       |a|");
+    }
+
+    #[test]
+    fn hexdump_styled() {
+        let dim = Style {
+            foreground_color: Color::DarkGray,
+            ..Default::default()
+        };
+        let red = Style {
+            foreground_color: Color::Red,
+            ..Default::default()
+        };
+        let dump = HexdumpDisplay {
+            highlights: &[(1..2, red)],
+            nul_style: dim,
+            offset_style: dim,
+            ..hexdump(b"\0A", 80)
+        };
+        assert_eq!(
+            dump.to_string(),
+            format!(
+                "\x1b[90m00000000\x1b[0m  \x1b[90m00\x1b[0m \x1b[31m41\x1b[0m{:45}|\
+                 \x1b[90m.\x1b[0m\x1b[31mA\x1b[0m|",
+                ""
+            )
+        );
+    }
+}
@@ -0,0 +1,143 @@
+//! Integration point for syntax highlighting.
+
+use core::ops::Range;
+
+use crate::Style;
+
+/// Syntax highlighter that assigns styles to parts of source code.
+///
+/// Renderers that display source code, such as [`SnippetDisplay`](crate::SnippetDisplay), accept
+/// a highlighter, so that this crate does not depend on a particular highlighting library. With
+/// the `syntect` feature, `SyntectHighlighter` implements this trait using the `syntect` crate.
+pub trait Highlighter {
+    /// Returns the styled spans of the source code, each represented by its byte range and style.
+    ///
+    /// The spans must be in ascending order and must not overlap. Text that is not covered by any
+    /// span is displayed in the default style.
+    #[must_use]
+    fn highlight(&self, source: &str) -> Vec<(Range<usize>, Style)>;
+}
+
+#[cfg(feature = "syntect")]
+pub use syntect_highlighter::SyntectHighlighter;
+
+/// [`Highlighter`] implementation using the `syntect` crate.
+#[cfg(feature = "syntect")]
+mod syntect_highlighter {
+    use core::ops::Range;
+
+    use syntect::{
+        easy::HighlightLines,
+        highlighting::{self, FontStyle, Theme},
+        parsing::{SyntaxReference, SyntaxSet},
+        util::LinesWithEndings,
+    };
+
+    use crate::{Color, Style};
+
+    use super::Highlighter;
+
+    /// [`Highlighter`] that highlights source code using a `syntect` syntax definition and theme.
+    ///
+    /// The foreground colors of the theme are mapped to the nearest [`Color`], and bold and
+    /// underlined font styles are retained. Background colors are ignored, so that the terminal
+    /// background is not painted. If highlighting fails, the rest of the source code is displayed
+    /// in the default style.
+    #[derive(Clone, Copy, Debug)]
+    #[expect(clippy::exhaustive_structs)]
+    pub struct SyntectHighlighter<'a> {
+        /// The syntax set containing the syntax definition.
+        pub syntax_set: &'a SyntaxSet,
+        /// The syntax definition of the source code's language.
+        pub syntax: &'a SyntaxReference,
+        /// The theme that assigns styles to the syntax elements.
+        pub theme: &'a Theme,
+    }
+
+    impl Highlighter for SyntectHighlighter<'_> {
+        fn highlight(&self, source: &str) -> Vec<(Range<usize>, Style)> {
+            let mut highlight_lines = HighlightLines::new(self.syntax, self.theme);
+            let mut spans = Vec::new();
+            let mut start = 0;
+            for line in LinesWithEndings::from(source) {
+                let Ok(regions) = highlight_lines.highlight_line(line, self.syntax_set) else {
+                    break;
+                };
+                for (style, text) in regions {
+                    let end = start + text.len();
+                    spans.push((start..end, convert_style(style)));
+                    start = end;
+                }
+            }
+            spans
+        }
+    }
+
+    /// Converts a `syntect` style to a [`Style`].
+    #[must_use]
+    fn convert_style(style: highlighting::Style) -> Style {
+        let color = style.foreground;
+        Style {
+            foreground_color: Color::nearest([color.r, color.g, color.b]),
+            bold: style.font_style.contains(FontStyle::BOLD),
+            underlined: style.font_style.contains(FontStyle::UNDERLINE),
+            italic: style.font_style.contains(FontStyle::ITALIC),
+            ..Default::default()
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use std::collections::HashMap;
+
+        use syntect::{
+            highlighting::{self, ThemeSettings},
+            parsing::{Scope, SyntaxDefinition, SyntaxSetBuilder, syntax_definition::Context},
+        };
+
+        use super::*;
+
+        #[test]
+        fn syntect_highlighter() {
+            let mut builder = SyntaxSetBuilder::new();
+            builder.add(SyntaxDefinition {
+                name: "Plain Text".to_owned(),
+                file_extensions: vec!["txt".to_owned()],
+                scope: Scope::new("text.plain").expect("valid scope"),
+                first_line_match: None,
+                hidden: false,
+                variables: HashMap::new(),
+                contexts: HashMap::from([
+                    ("__start".to_owned(), Context::new(false)),
+                    ("main".to_owned(), Context::new(true)),
+                ]),
+            });
+            let syntax_set = builder.build();
+            let theme = Theme {
+                settings: ThemeSettings {
+                    foreground: Some(highlighting::Color {
+                        r: 0,
+                        g: 200,
+                        b: 0,
+                        a: 255,
+                    }),
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
+            let highlighter = SyntectHighlighter {
+                syntax_set: &syntax_set,
+                syntax: syntax_set.find_syntax_plain_text(),
+                theme: &theme,
+            };
+            let green = Style {
+                foreground_color: Color::Green,
+                ..Default::default()
+            };
+            assert_eq!(
+                highlighter.highlight("foo\nbar"),
+                [(0..4, green), (4..7, green)]
+            );
+        }
+    }
+}
@@ -0,0 +1,225 @@
+//! Export of styled output as HTML.
+
+use core::fmt::Write as _;
+use std::io::{self, Write};
+
+use crate::{ScriptPosition, Style, is_sequence_start, skip_sequence};
+
+/// Start of a standalone HTML document, up to the start of the preformatted output.
+const HTML_START: &str = "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+                          <title>Terminal output</title>\n<style>\n\
+                          pre { background-color: #000000; color: #e5e5e5; padding: 1em; }\n\
+                          </style>\n</head>\n<body>\n<pre>";
+
+/// End of a standalone HTML document, after the end of the preformatted output.
+const HTML_END: &str = "</pre>\n</body>\n</html>\n";
+
+/// Adapter that records everything written to a stream, so that it can be exported as an HTML
+/// document preserving colors and text attributes.
+///
+/// The output is written to the stream unchanged. To record output without displaying it, use
+/// [`io::sink`] as the stream. The recorded output is converted to HTML by [`html`](Self::html):
+/// Select Graphic Rendition (SGR) control sequences that set the colors and attributes supported by
+/// [`Style`] are converted to styled `<span>` elements, and other escape sequences are removed.
+#[derive(Debug)]
+pub struct HtmlRecorder<W>
+where
+    W: Write,
+{
+    /// The stream to which output is written.
+    stream: W,
+    /// The output written to the stream.
+    recorded: Vec<u8>,
+}
+
+impl<W> HtmlRecorder<W>
+where
+    W: Write,
+{
+    /// Creates an adapter that writes output to `stream` and records it.
+    #[inline]
+    #[must_use]
+    pub const fn new(stream: W) -> Self {
+        Self {
+            stream,
+            recorded: Vec::new(),
+        }
+    }
+
+    /// Returns a standalone HTML document containing the recorded output.
+    ///
+    /// Invalid UTF-8 in the output is replaced by U+FFFD.
+    #[must_use]
+    pub fn html(&self) -> String {
+        let text = String::from_utf8_lossy(&self.recorded);
+        let mut html = String::from(HTML_START);
+        let mut style = Style::default();
+        let mut rest = text.as_ref();
+        while !rest.is_empty() {
+            let text_len = rest.find(is_sequence_start).unwrap_or(rest.len());
+            if text_len != 0 {
+                write_span(&mut html, &rest[..text_len], style);
+                rest = &rest[text_len..];
+                continue;
+            }
+            let after = skip_sequence(rest);
+            if let Some(parameters) = rest[..rest.len() - after.len()]
+                .strip_prefix("\x1b[")
+                .and_then(|sequence| sequence.strip_suffix('m'))
+            {
+                style = style.with_sgr_parameters(parameters);
+            }
+            rest = after;
+        }
+        html.push_str(HTML_END);
+        html
+    }
+
+    /// Returns a reference to the stream.
+    #[inline]
+    #[must_use]
+    pub const fn get_ref(&self) -> &W {
+        &self.stream
+    }
+
+    /// Returns a mutable reference to the stream.
+    ///
+    /// Output that is written directly to the stream is not recorded.
+    #[inline]
+    #[must_use]
+    pub const fn get_mut(&mut self) -> &mut W {
+        &mut self.stream
+    }
+
+    /// Returns the stream.
+    #[inline]
+    #[must_use]
+    pub fn into_inner(self) -> W {
+        self.stream
+    }
+}
+
+impl<W> Write for HtmlRecorder<W>
+where
+    W: Write,
+{
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let len = self.stream.write(buf)?;
+        self.recorded.extend_from_slice(&buf[..len]);
+        Ok(len)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        self.stream.flush()
+    }
+}
+
+/// Appends the text, escaped for HTML, to the HTML code, enclosed in a `<span>` element if the
+/// style is not the default style.
+fn write_span(html: &mut String, text: &str, style: Style) {
+    let mut css = String::new();
+    let (foreground, background) = if style.reverse {
+        // The default colors are those of the `pre` element in `HTML_START`.
+        (
+            Some(style.background_color.rgb().unwrap_or([0x00, 0x00, 0x00])),
+            Some(style.foreground_color.rgb().unwrap_or([0xe5, 0xe5, 0xe5])),
+        )
+    } else {
+        (style.foreground_color.rgb(), style.background_color.rgb())
+    };
+    if let Some([red, green, blue]) = foreground {
+        _ = write!(css, "color: #{red:02x}{green:02x}{blue:02x}; ");
+    }
+    if let Some([red, green, blue]) = background {
+        _ = write!(css, "background-color: #{red:02x}{green:02x}{blue:02x}; ");
+    }
+    if style.bold {
+        css.push_str("font-weight: bold; ");
+    }
+    if style.dim {
+        css.push_str("opacity: 0.5; ");
+    }
+    if style.italic {
+        css.push_str("font-style: italic; ");
+    }
+    let decorations: Vec<&str> = [
+        (style.underlined, "underline"),
+        (style.strikethrough, "line-through"),
+        (style.blinking, "blink"),
+    ]
+    .into_iter()
+    .filter_map(|(enabled, decoration)| enabled.then_some(decoration))
+    .collect();
+    if !decorations.is_empty() {
+        _ = write!(css, "text-decoration: {}; ", decorations.join(" "));
+    }
+    match style.script_position {
+        Some(ScriptPosition::Superscript) => css.push_str("vertical-align: super; "),
+        Some(ScriptPosition::Subscript) => css.push_str("vertical-align: sub; "),
+        None => {}
+    }
+
+    if !css.is_empty() {
+        _ = write!(html, "<span style=\"{}\">", css.trim_end());
+    }
+    for ch in text.chars() {
+        match ch {
+            '&' => html.push_str("&amp;"),
+            '<' => html.push_str("&lt;"),
+            '>' => html.push_str("&gt;"),
+            '"' => html.push_str("&quot;"),
+            _ => html.push(ch),
+        }
+    }
+    if !css.is_empty() {
+        html.push_str("</span>");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the HTML document containing the output written to an HTML recorder.
+    fn record(output: &str) -> String {
+        let mut recorder = HtmlRecorder::new(Vec::new());
+        recorder
+            .write_all(output.as_bytes())
+            .expect("writing to a vector succeeds");
+        assert_eq!(recorder.get_ref().as_slice(), output.as_bytes());
+        recorder.html()
+    }
+
+    #[test]
+    fn html_plain() {
+        assert_eq!(
+            record("a < b & c\n"),
+            format!("{HTML_START}a &lt; b &amp; c\n{HTML_END}")
+        );
+    }
+
+    #[test]
+    fn html_styled() {
+        assert_eq!(
+            record("\x1b[1;31merror\x1b[0m: \x1b[4;102mbad\x1b[24m\x1b[?25l!\x1b[0m"),
+            format!(
+                "{HTML_START}<span style=\"color: #cd0000; font-weight: bold;\">error</span>: \
+                 <span style=\"background-color: #00ff00; text-decoration: underline;\">bad</span>\
+                 <span style=\"background-color: #00ff00;\">!</span>{HTML_END}"
+            )
+        );
+    }
+
+    #[test]
+    fn html_attributes() {
+        assert_eq!(
+            record("\x1b[3;4;9mfoo\x1b[0;7;31mbar"),
+            format!(
+                "{HTML_START}<span style=\"font-style: italic; \
+                 text-decoration: underline line-through;\">foo</span>\
+                 <span style=\"color: #000000; background-color: #cd0000;\">bar</span>{HTML_END}"
+            )
+        );
+    }
+}
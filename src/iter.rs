@@ -0,0 +1,254 @@
+//! Iterator adapters for styling, wrapping, and laying out items lazily.
+
+use core::{fmt::Display, iter};
+use std::collections::VecDeque;
+
+use crate::{Style, StyledDisplay, truncate_styled, width::display_width, wrap::wrap_line};
+
+/// Number of spaces between the columns of columnized items.
+const COLUMN_GAP: usize = 2;
+
+/// Extension trait that adds styling, wrapping, and layout adapters to iterators.
+///
+/// The adapters are lazy: items are styled, wrapped, or laid out one at a time as the iterator is
+/// advanced, so large inputs can be rendered without collecting them first.
+pub trait StyledIteratorExt: Iterator + Sized {
+    /// Returns an iterator that displays each item in the style returned by `style_for`.
+    #[inline]
+    fn styled_with<F>(self, style_for: F) -> StyledWith<Self, F>
+    where
+        Self::Item: Display,
+        F: FnMut(&Self::Item) -> Style,
+    {
+        StyledWith {
+            iter: self,
+            style_for,
+        }
+    }
+
+    /// Returns an iterator over the lines of the items wrapped so that each line occupies at most
+    /// `width` columns.
+    ///
+    /// Each item starts on a new line, and line feeds within items start new lines. Lines are
+    /// broken at spaces, which are removed at the line breaks, and words that are longer than
+    /// `width` are broken at character boundaries. A `width` of zero is treated as one.
+    #[inline]
+    fn wrapped(self, width: usize) -> Wrapped<Self>
+    where
+        Self::Item: AsRef<str>,
+    {
+        Wrapped {
+            iter: self,
+            width,
+            lines: VecDeque::new(),
+        }
+    }
+
+    /// Returns an iterator over lines of at most `width` columns, each of which contains as many
+    /// items as fit side by side in columns of `column_width` columns.
+    ///
+    /// The items are laid out from left to right and then from top to bottom, so that each line
+    /// only depends on the items it contains. Since the widths of the items are not known in
+    /// advance, the column width must be specified. Items that are wider are truncated at the end
+    /// and marked by an ellipsis (`…`), with ANSI escape sequences retained as by
+    /// [`truncate_styled`], so styled items, e.g., from [`styled_with`](Self::styled_with), can be
+    /// laid out as well. The columns are separated by two spaces, and each line contains at least
+    /// one item, which is truncated to `width` columns if the column width exceeds it.
+    #[inline]
+    fn columnized(self, width: usize, column_width: usize) -> Columnized<Self>
+    where
+        Self::Item: Display,
+    {
+        Columnized {
+            iter: self,
+            column_width: column_width.min(width),
+            columns: ((width + COLUMN_GAP) / (column_width + COLUMN_GAP)).max(1),
+        }
+    }
+}
+
+impl<I> StyledIteratorExt for I where I: Iterator {}
+
+/// Iterator that displays each item of another iterator in a style determined by a function.
+///
+/// This is returned by [`StyledIteratorExt::styled_with`].
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct StyledWith<I, F> {
+    /// The iterator over the items.
+    iter: I,
+    /// The function that returns the style of an item.
+    style_for: F,
+}
+
+impl<I, F> Iterator for StyledWith<I, F>
+where
+    I: Iterator,
+    I::Item: Display,
+    F: FnMut(&I::Item) -> Style,
+{
+    type Item = StyledDisplay<I::Item>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let value = self.iter.next()?;
+        Some(StyledDisplay {
+            style: (self.style_for)(&value),
+            value,
+        })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+/// Iterator over the lines of the items of another iterator, wrapped to a width.
+///
+/// This is returned by [`StyledIteratorExt::wrapped`].
+#[derive(Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct Wrapped<I> {
+    /// The iterator over the items.
+    iter: I,
+    /// The maximum number of columns occupied by a line.
+    width: usize,
+    /// The wrapped lines of the current item that have not been returned yet.
+    lines: VecDeque<String>,
+}
+
+impl<I> Iterator for Wrapped<I>
+where
+    I: Iterator,
+    I::Item: AsRef<str>,
+{
+    type Item = String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.lines.is_empty() {
+            let item = self.iter.next()?;
+            self.lines.extend(
+                item.as_ref()
+                    .split('\n')
+                    .flat_map(|line| wrap_line(line, self.width))
+                    .map(str::to_owned),
+            );
+        }
+        self.lines.pop_front()
+    }
+}
+
+/// Iterator over lines containing the items of another iterator laid out in columns.
+///
+/// This is returned by [`StyledIteratorExt::columnized`].
+#[derive(Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct Columnized<I> {
+    /// The iterator over the items.
+    iter: I,
+    /// The maximum number of columns occupied by an item.
+    column_width: usize,
+    /// The number of items per line.
+    columns: usize,
+}
+
+impl<I> Iterator for Columnized<I>
+where
+    I: Iterator,
+    I::Item: Display,
+{
+    type Item = String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut line = String::new();
+        // The spaces between the previous item and the next one, or `None` if there is no previous
+        // item in the line.
+        let mut padding = None;
+        for item in self.iter.by_ref().take(self.columns) {
+            if let Some(padding) = padding {
+                line.extend(iter::repeat_n(' ', padding));
+            }
+            let item = item.to_string();
+            let cell = truncate_styled(&item, self.column_width);
+            line.push_str(&cell);
+            padding = Some(self.column_width.saturating_sub(display_width(&cell)) + COLUMN_GAP);
+        }
+        padding.map(|_| line)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Color;
+
+    use super::*;
+
+    #[test]
+    fn styled_with_items() {
+        let red = Style {
+            foreground_color: Color::Red,
+            ..Default::default()
+        };
+        let styled: Vec<String> = ["ok", "error: oops"]
+            .into_iter()
+            .styled_with(|line| {
+                if line.starts_with("error") {
+                    red
+                } else {
+                    Style::default()
+                }
+            })
+            .map(|item| item.to_string())
+            .collect();
+        assert_eq!(styled, ["ok", "\x1b[31merror: oops\x1b[0m"]);
+    }
+
+    #[test]
+    fn wrapped_items() {
+        let lines: Vec<String> = ["the quick brown fox", "", "jumps\nover"]
+            .into_iter()
+            .wrapped(10)
+            .collect();
+        assert_eq!(lines, ["the quick", "brown fox", "", "jumps", "over"]);
+    }
+
+    #[test]
+    fn columnized_items() {
+        let lines: Vec<String> = ["foo", "bar", "a_long_name", "x", "y"]
+            .into_iter()
+            .columnized(22, 6)
+            .collect();
+        assert_eq!(lines, ["foo     bar     a_lon…", "x       y"]);
+        assert_eq!(["foo", "bar"].into_iter().columnized(20, 6).count(), 1);
+        assert_eq!(
+            ["foo", "bar"]
+                .into_iter()
+                .columnized(2, 6)
+                .collect::<Vec<_>>(),
+            ["f…", "b…"]
+        );
+        assert_eq!(iter::empty::<&str>().columnized(20, 6).next(), None);
+    }
+
+    #[test]
+    fn columnized_styled_items() {
+        let bold = Style {
+            bold: true,
+            ..Default::default()
+        };
+        let lines: Vec<String> = ["ok", "error"]
+            .into_iter()
+            .styled_with(|_| bold)
+            .columnized(20, 4)
+            .collect();
+        assert_eq!(lines, ["\x1b[1mok\x1b[0m    \x1b[1merr…\x1b[0m"]);
+    }
+
+    #[test]
+    fn wrapped_lazily() {
+        let mut lines = (0..).map(|index| format!("item {index}")).wrapped(4);
+        assert_eq!(lines.next().as_deref(), Some("item"));
+        assert_eq!(lines.next().as_deref(), Some("0"));
+        assert_eq!(lines.next().as_deref(), Some("item"));
+    }
+}
@@ -0,0 +1,162 @@
+//! Aligned key-value lists.
+
+use core::fmt::{self, Display, Formatter};
+
+use crate::{StyledDisplay, width::display_width, wrap::wrap_line};
+
+/// List of key-value pairs whose values are aligned on a common column.
+///
+/// When `KeyValueDisplay` is formatted or converted to a string, each key is followed by the
+/// [`separator`](Self::separator), and all values start at the column after the widest key and
+/// separator. Values are wrapped so that no line exceeds [`width`](Self::width) columns, and
+/// continuation lines, as well as further lines of values that contain line feeds, are indented to
+/// the value column. Keys and values are displayed in their styles, with the styles reset at the
+/// end of each line. The lines are separated by line feeds, and the last line is not terminated.
+#[expect(clippy::exhaustive_structs)]
+pub struct KeyValueDisplay<'a> {
+    /// The key-value pairs.
+    pub entries: &'a [(StyledDisplay<&'a str>, StyledDisplay<&'a str>)],
+    /// The text written between each key and the padding before its value, e.g., `": "`.
+    pub separator: &'a str,
+    /// The maximum number of columns occupied by a line.
+    pub width: usize,
+}
+
+impl Display for KeyValueDisplay<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let separator_width = display_width(self.separator);
+        let value_column = self
+            .entries
+            .iter()
+            .map(|(key, _)| display_width(key.value) + separator_width)
+            .max()
+            .unwrap_or(0);
+        let value_width = self.width.saturating_sub(value_column);
+
+        for (index, (key, value)) in self.entries.iter().enumerate() {
+            if index != 0 {
+                f.write_str("\n")?;
+            }
+            write!(f, "{key}{}", self.separator)?;
+            let mut padding = value_column - display_width(key.value) - separator_width;
+
+            let lines = value
+                .value
+                .split('\n')
+                .flat_map(|line| wrap_line(line, value_width));
+            for (line_index, line) in lines.enumerate() {
+                if line_index != 0 {
+                    f.write_str("\n")?;
+                    padding = value_column;
+                }
+                if !line.is_empty() {
+                    let styled = StyledDisplay {
+                        style: value.style,
+                        value: line,
+                    };
+                    write!(f, "{:padding$}{styled}", "")?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Color, Style};
+
+    use super::*;
+
+    /// Returns the text in the default style.
+    fn plain(value: &str) -> StyledDisplay<&str> {
+        StyledDisplay {
+            style: Style::default(),
+            value,
+        }
+    }
+
+    #[test]
+    fn key_value_empty() {
+        let list = KeyValueDisplay {
+            entries: &[],
+            separator: ": ",
+            width: 80,
+        };
+        assert_eq!(list.to_string(), "");
+    }
+
+    #[test]
+    fn key_value_aligned() {
+        let list = KeyValueDisplay {
+            entries: &[
+                (plain("Name"), plain("styled-output")),
+                (plain("Version"), plain("0.0.1")),
+                (plain("Description"), plain("")),
+            ],
+            separator: ": ",
+            width: 80,
+        };
+        assert_eq!(
+            list.to_string(),
+            "Name:        styled-output\nVersion:     0.0.1\nDescription: "
+        );
+    }
+
+    #[test]
+    fn key_value_wrapped() {
+        let list = KeyValueDisplay {
+            entries: &[
+                (plain("Id"), plain("42")),
+                (plain("Text"), plain("the quick brown fox\njumps")),
+            ],
+            separator: " = ",
+            width: 17,
+        };
+        assert_eq!(
+            list.to_string(),
+            "Id =   42\nText = the quick\n       brown fox\n       jumps"
+        );
+    }
+
+    #[test]
+    fn key_value_styled() {
+        let bold = Style {
+            bold: true,
+            ..Default::default()
+        };
+        let red = Style {
+            foreground_color: Color::Red,
+            ..Default::default()
+        };
+        let list = KeyValueDisplay {
+            entries: &[
+                (
+                    StyledDisplay {
+                        style: bold,
+                        value: "Error",
+                    },
+                    StyledDisplay {
+                        style: red,
+                        value: "file not found",
+                    },
+                ),
+                (
+                    StyledDisplay {
+                        style: bold,
+                        value: "Path",
+                    },
+                    plain("/tmp/x"),
+                ),
+            ],
+            separator: ":",
+            width: 12,
+        };
+        assert_eq!(
+            list.to_string(),
+            "\x1b[1mError\x1b[0m:\x1b[31mfile\x1b[0m\n      \x1b[31mnot\x1b[0m\n      \
+             \x1b[31mfound\x1b[0m\n\x1b[1mPath\x1b[0m: /tmp/x"
+        );
+    }
+}
@@ -0,0 +1,117 @@
+//! Legends explaining the meaning of styles.
+
+use core::fmt::{self, Display, Formatter};
+
+use crate::{Style, StyledDisplay, width::display_width};
+
+/// Number of spaces between the columns of a legend.
+const COLUMN_GAP: usize = 2;
+
+/// Legend that explains the meaning of styles, e.g., the colors of a chart or heatmap.
+///
+/// When `LegendDisplay` is formatted or converted to a string, each entry is displayed as the
+/// [`swatch`](Self::swatch) in the entry's style, followed by a space and the entry's label. The
+/// entries are laid out from left to right and top to bottom in as many columns of equal width as
+/// fit into [`width`](Self::width) columns, but at least one, with two spaces between the columns.
+/// The lines are separated by line feeds, and the last line is not terminated.
+///
+/// ```text
+/// ■ added    ■ removed
+/// ■ changed
+/// ```
+#[derive(Clone, Copy, Debug)]
+#[expect(clippy::exhaustive_structs)]
+pub struct LegendDisplay<'a> {
+    /// The styles and their labels.
+    pub entries: &'a [(Style, &'a str)],
+    /// The text displayed in the style of each entry, e.g., `"■"`.
+    pub swatch: &'a str,
+    /// The maximum number of columns occupied by a line.
+    pub width: usize,
+}
+
+impl Display for LegendDisplay<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let swatch_width = display_width(self.swatch);
+        let column_width = self
+            .entries
+            .iter()
+            .map(|(_, label)| swatch_width + 1 + display_width(label))
+            .max()
+            .unwrap_or(0);
+        let columns = ((self.width + COLUMN_GAP) / (column_width + COLUMN_GAP)).max(1);
+
+        for (row, entries) in self.entries.chunks(columns).enumerate() {
+            if row != 0 {
+                f.write_str("\n")?;
+            }
+            // Padding after the previous entry of the row.
+            let mut padding = 0;
+            for &(style, label) in entries {
+                let swatch = StyledDisplay {
+                    style,
+                    value: self.swatch,
+                };
+                write!(f, "{:padding$}{swatch} {label}", "")?;
+                padding = column_width - swatch_width - 1 - display_width(label) + COLUMN_GAP;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Color;
+
+    use super::*;
+
+    /// Returns a style with the foreground color.
+    fn foreground(color: Color) -> Style {
+        Style {
+            foreground_color: color,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn legend_columns() {
+        let legend = LegendDisplay {
+            entries: &[
+                (Style::default(), "added"),
+                (Style::default(), "removed"),
+                (Style::default(), "changed"),
+            ],
+            swatch: "■",
+            width: 20,
+        };
+        assert_eq!(legend.to_string(), "■ added    ■ removed\n■ changed");
+    }
+
+    #[test]
+    fn legend_single_column() {
+        let legend = LegendDisplay {
+            entries: &[(Style::default(), "low"), (Style::default(), "high")],
+            swatch: "##",
+            width: 5,
+        };
+        assert_eq!(legend.to_string(), "## low\n## high");
+    }
+
+    #[test]
+    fn legend_styled() {
+        let legend = LegendDisplay {
+            entries: &[
+                (foreground(Color::Green), "ok"),
+                (foreground(Color::Red), "failed"),
+            ],
+            swatch: "●",
+            width: 80,
+        };
+        assert_eq!(
+            legend.to_string(),
+            "\x1b[32m●\x1b[0m ok      \x1b[31m●\x1b[0m failed"
+        );
+    }
+}
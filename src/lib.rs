@@ -1,7 +1,59 @@
 //! Output styling.
 
+mod annotate;
+mod asciicast;
+mod bar_chart;
+mod bidi;
+mod conditional;
+mod diagnostic;
 mod display;
+mod glyphs;
+mod guard;
+mod hexdump;
+mod highlight;
+mod html;
+mod iter;
+mod key_value;
+mod legend;
+mod numeric;
+mod pad;
+pub mod registry;
+mod sanitize;
+mod side_by_side;
+mod snippet;
+mod status;
 mod style;
+#[cfg(feature = "termcolor")]
+mod termcolor_interop;
+pub mod terminal;
+mod truncate;
+mod utf8;
+mod width;
+mod wrap;
+mod writer;
 
+pub use annotate::*;
+pub use asciicast::*;
+pub use bar_chart::*;
+pub use bidi::*;
+pub use conditional::*;
+pub use diagnostic::*;
 pub use display::*;
+pub use glyphs::*;
+pub use hexdump::*;
+pub use highlight::*;
+pub use html::*;
+pub use iter::*;
+pub use key_value::*;
+pub use legend::*;
+pub use numeric::*;
+pub use pad::*;
+pub use sanitize::*;
+pub use side_by_side::*;
+pub use snippet::*;
+pub use status::*;
 pub use style::*;
+pub use truncate::*;
+pub use width::*;
+pub use wrap::*;
+pub use writer::*;
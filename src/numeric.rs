@@ -0,0 +1,269 @@
+//! Formatting of numbers.
+
+use core::fmt::Display;
+
+use crate::{Style, StyledDisplay, width::display_width};
+
+/// Format of numbers, e.g., for columns of a report.
+///
+/// Numbers are formatted with their [`Display`] implementation and the
+/// [`precision`](Self::precision), after which the digits of the integer part are grouped and the
+/// decimal point is replaced. Values that do not start with digits (optionally preceded by a sign),
+/// such as `NaN` or `inf`, are not grouped.
+///
+/// When the value returned by [`styled`](Self::styled) is formatted with a width, the padding is
+/// computed on the formatted number only, not on the ANSI control sequences that style it.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[expect(clippy::exhaustive_structs)]
+pub struct NumberFormat<'a> {
+    /// The number of digits after the decimal point, or `None` for the default precision of the
+    /// value's [`Display`] implementation. Integers are not affected by the precision.
+    pub precision: Option<usize>,
+    /// The character inserted between groups of three digits of the integer part, or `None` if
+    /// digits are not grouped.
+    pub thousands_separator: Option<char>,
+    /// The character that replaces the decimal point.
+    pub decimal_separator: char,
+    /// The text appended to the number, e.g., a unit such as `" ms"`.
+    pub suffix: &'a str,
+}
+
+impl Default for NumberFormat<'_> {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            precision: None,
+            thousands_separator: None,
+            decimal_separator: '.',
+            suffix: "",
+        }
+    }
+}
+
+impl NumberFormat<'_> {
+    /// Returns the formatted value.
+    #[must_use]
+    pub fn format<T>(&self, value: T) -> String
+    where
+        T: Display,
+    {
+        let formatted = self.precision.map_or_else(
+            || value.to_string(),
+            |precision| format!("{value:.precision$}"),
+        );
+        let sign_len = formatted.len() - formatted.trim_start_matches(['-', '+']).len();
+        let digits_len = formatted[sign_len..]
+            .find(|ch: char| !ch.is_ascii_digit())
+            .unwrap_or(formatted.len() - sign_len);
+        let (integer, rest) = formatted.split_at(sign_len + digits_len);
+
+        let mut result =
+            String::with_capacity(formatted.len() + digits_len / 3 + self.suffix.len());
+        result.push_str(&integer[..sign_len]);
+        for (index, digit) in integer[sign_len..].chars().enumerate() {
+            if let Some(separator) = self.thousands_separator
+                && index != 0
+                && (digits_len - index) % 3 == 0
+            {
+                result.push(separator);
+            }
+            result.push(digit);
+        }
+        match rest.strip_prefix('.') {
+            Some(fraction) if digits_len != 0 => {
+                result.push(self.decimal_separator);
+                result.push_str(fraction);
+            }
+            _ => result.push_str(rest),
+        }
+        result.push_str(self.suffix);
+        result
+    }
+
+    /// Returns the formatted value in the specified style.
+    #[inline]
+    #[must_use]
+    pub fn styled<T>(&self, value: T, style: Style) -> StyledDisplay<String>
+    where
+        T: Display,
+    {
+        StyledDisplay {
+            style,
+            value: self.format(value),
+        }
+    }
+}
+
+/// Alignment of formatted numbers in a column on their decimal separator.
+///
+/// The alignment is computed from all values in the column. Integer parts are right-aligned, and
+/// fractional parts (including the decimal separator) are left-aligned, so that the decimal
+/// separators of all values are in the same column. Values without a decimal separator are
+/// right-aligned at the decimal separator column. Widths are measured in terminal columns.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct DecimalAlignment {
+    /// The decimal separator.
+    separator: char,
+    /// The number of columns occupied by the widest integer part.
+    integer_width: usize,
+    /// The number of columns occupied by the widest fractional part, including the separator.
+    fraction_width: usize,
+}
+
+impl DecimalAlignment {
+    /// Computes the alignment of the specified formatted values on the specified decimal separator.
+    #[must_use]
+    pub fn new<'a, I>(values: I, separator: char) -> Self
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        let mut alignment = Self {
+            separator,
+            integer_width: 0,
+            fraction_width: 0,
+        };
+        for value in values {
+            let (integer, fraction) = alignment.split(value);
+            alignment.integer_width = alignment.integer_width.max(display_width(integer));
+            alignment.fraction_width = alignment.fraction_width.max(display_width(fraction));
+        }
+        alignment
+    }
+
+    /// Returns the number of columns occupied by each aligned value.
+    #[inline]
+    #[must_use]
+    pub const fn width(&self) -> usize {
+        self.integer_width + self.fraction_width
+    }
+
+    /// Returns the number of spaces to insert before and after the value to align it.
+    ///
+    /// Values that were not passed to [`new`](Self::new) may not fit, in which case the missing
+    /// padding is zero.
+    #[must_use]
+    pub fn padding(&self, value: &str) -> (usize, usize) {
+        let (integer, fraction) = self.split(value);
+        (
+            self.integer_width.saturating_sub(display_width(integer)),
+            self.fraction_width.saturating_sub(display_width(fraction)),
+        )
+    }
+
+    /// Returns the value aligned in the column.
+    ///
+    /// The padding is inserted outside of the style of the value.
+    #[must_use]
+    pub fn align(&self, value: &StyledDisplay<&str>) -> String {
+        let (before, after) = self.padding(value.value);
+        format!("{:before$}{value}{:after$}", "", "")
+    }
+
+    /// Splits the value into the integer part and the fractional part, which includes the decimal
+    /// separator.
+    #[inline]
+    #[must_use]
+    fn split<'a>(&self, value: &'a str) -> (&'a str, &'a str) {
+        value.split_at(value.find(self.separator).unwrap_or(value.len()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Color;
+
+    use super::*;
+
+    /// Returns the text in the default style.
+    fn plain(value: &str) -> StyledDisplay<&str> {
+        StyledDisplay {
+            style: Style::default(),
+            value,
+        }
+    }
+
+    #[test]
+    fn decimal_alignment() {
+        let values = ["1.5", "-12.25", "100", "3.125", ""];
+        let alignment = DecimalAlignment::new(values, '.');
+        assert_eq!(alignment.width(), 7);
+        let result: Vec<_> = values
+            .iter()
+            .map(|value| alignment.align(&plain(value)))
+            .collect();
+        assert_eq!(
+            result,
+            ["  1.5  ", "-12.25 ", "100    ", "  3.125", "       "]
+        );
+    }
+
+    #[test]
+    fn decimal_alignment_comma() {
+        let alignment = DecimalAlignment::new(["1.000,5", "20"], ',');
+        assert_eq!(alignment.padding("1.000,5"), (0, 0));
+        assert_eq!(alignment.padding("20"), (3, 2));
+        assert_eq!(alignment.padding("123.456,75"), (0, 0));
+    }
+
+    #[test]
+    fn decimal_alignment_styled() {
+        let alignment = DecimalAlignment::new(["-1.25", "3"], '.');
+        let styled = StyledDisplay {
+            style: Style {
+                foreground_color: Color::Green,
+                ..Default::default()
+            },
+            value: "3",
+        };
+        assert_eq!(alignment.align(&styled), " \x1b[32m3\x1b[0m   ");
+    }
+
+    #[test]
+    fn number_format_default() {
+        let format = NumberFormat::default();
+        assert_eq!(format.format(1_234_567), "1234567");
+        assert_eq!(format.format(-2.5), "-2.5");
+    }
+
+    #[test]
+    fn number_format_thousands_separator() {
+        let format = NumberFormat {
+            thousands_separator: Some(','),
+            ..Default::default()
+        };
+        assert_eq!(format.format(0), "0");
+        assert_eq!(format.format(999), "999");
+        assert_eq!(format.format(1000), "1,000");
+        assert_eq!(format.format(-1_234_567), "-1,234,567");
+        assert_eq!(format.format(12_345.678_9), "12,345.6789");
+    }
+
+    #[test]
+    fn number_format_precision_and_separators() {
+        let format = NumberFormat {
+            precision: Some(2),
+            thousands_separator: Some('.'),
+            decimal_separator: ',',
+            suffix: " €",
+        };
+        assert_eq!(format.format(1_234.5), "1.234,50 €");
+        assert_eq!(format.format(42), "42 €");
+        assert_eq!(format.format(f64::NAN), "NaN €");
+        assert_eq!(format.format(f64::NEG_INFINITY), "-inf €");
+    }
+
+    #[test]
+    fn number_format_styled_padding() {
+        let format = NumberFormat {
+            precision: Some(1),
+            suffix: " ms",
+            ..Default::default()
+        };
+        let style = Style {
+            foreground_color: Color::Red,
+            ..Default::default()
+        };
+        let result = format!("[{:>9}]", format.styled(12.34, style));
+        assert_eq!(result, "[\x1b[31m  12.3 ms\x1b[0m]");
+    }
+}
@@ -0,0 +1,135 @@
+//! Padding of text to a width.
+
+use std::borrow::Cow;
+
+use crate::{
+    StyledDisplay,
+    width::{char_width, display_width},
+};
+
+/// Returns the text padded on the left to at least `width` columns, i.e., aligned to the right.
+///
+/// The text may contain ANSI escape sequences, which do not count towards its width. The padding
+/// consists of the fill character, displayed in its style. If the fill character is wide and the
+/// padding is not a multiple of its width, or if it occupies no columns, the remaining columns are
+/// filled with spaces. The text is borrowed if it needs no padding.
+#[must_use]
+pub fn pad_left<'a>(text: &'a str, width: usize, fill: &StyledDisplay<char>) -> Cow<'a, str> {
+    let columns = width.saturating_sub(display_width(text));
+    if columns == 0 {
+        return Cow::Borrowed(text);
+    }
+    Cow::Owned(format!("{}{text}", padding(columns, fill)))
+}
+
+/// Returns the text padded on the right to at least `width` columns, i.e., aligned to the left.
+///
+/// The text and the padding are handled as described for [`pad_left`].
+#[must_use]
+pub fn pad_right<'a>(text: &'a str, width: usize, fill: &StyledDisplay<char>) -> Cow<'a, str> {
+    let columns = width.saturating_sub(display_width(text));
+    if columns == 0 {
+        return Cow::Borrowed(text);
+    }
+    Cow::Owned(format!("{text}{}", padding(columns, fill)))
+}
+
+/// Returns the text padded on both sides to at least `width` columns, i.e., centered.
+///
+/// If the padding cannot be divided evenly, the right side receives one more column. The text and
+/// the padding are handled as described for [`pad_left`].
+#[must_use]
+pub fn pad_center<'a>(text: &'a str, width: usize, fill: &StyledDisplay<char>) -> Cow<'a, str> {
+    let columns = width.saturating_sub(display_width(text));
+    if columns == 0 {
+        return Cow::Borrowed(text);
+    }
+    let left = columns / 2;
+    Cow::Owned(format!(
+        "{}{text}{}",
+        padding(left, fill),
+        padding(columns - left, fill)
+    ))
+}
+
+/// Returns padding that occupies the specified number of columns, consisting of the fill character
+/// in its style and spaces for the remaining columns.
+#[must_use]
+fn padding(columns: usize, fill: &StyledDisplay<char>) -> String {
+    let fill_width = char_width(fill.value);
+    if columns == 0 || fill_width == 0 {
+        return " ".repeat(columns);
+    }
+    let count = columns / fill_width;
+    let remainder = columns % fill_width;
+    if count == 0 {
+        return " ".repeat(remainder);
+    }
+    let fill_chars = StyledDisplay {
+        style: fill.style,
+        value: core::iter::repeat_n(fill.value, count).collect::<String>(),
+    };
+    format!("{fill_chars}{:remainder$}", "")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Color, Style};
+
+    use super::*;
+
+    /// Returns the fill character in the default style.
+    fn plain(value: char) -> StyledDisplay<char> {
+        StyledDisplay {
+            style: Style::default(),
+            value,
+        }
+    }
+
+    #[test]
+    fn pad_plain() {
+        assert_eq!(pad_left("foo", 6, &plain(' ')), "   foo");
+        assert_eq!(pad_right("foo", 6, &plain('.')), "foo...");
+        assert_eq!(pad_center("foo", 8, &plain('-')), "--foo---");
+    }
+
+    #[test]
+    fn pad_fits() {
+        assert!(matches!(
+            pad_left("foo", 3, &plain(' ')),
+            Cow::Borrowed("foo")
+        ));
+        assert!(matches!(
+            pad_center("foobar", 3, &plain(' ')),
+            Cow::Borrowed("foobar")
+        ));
+    }
+
+    #[test]
+    fn pad_wide_and_styled_text() {
+        assert_eq!(pad_right("日本", 6, &plain(' ')), "日本  ");
+        assert_eq!(
+            pad_left("\x1b[1mfoo\x1b[0m", 5, &plain(' ')),
+            "  \x1b[1mfoo\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn pad_wide_fill() {
+        assert_eq!(pad_right("a", 6, &plain('＊')), "a＊＊ ");
+        assert_eq!(pad_left("a", 2, &plain('＊')), " a");
+        assert_eq!(pad_left("a", 3, &plain('\u{301}')), "  a");
+    }
+
+    #[test]
+    fn pad_styled_fill() {
+        let fill = StyledDisplay {
+            style: Style {
+                foreground_color: Color::DarkGray,
+                ..Default::default()
+            },
+            value: '.',
+        };
+        assert_eq!(pad_right("foo", 6, &fill), "foo\x1b[90m...\x1b[0m");
+    }
+}
@@ -0,0 +1,134 @@
+//! Process-wide registry of named text styles.
+//!
+//! The registry maps string keys such as `"warning"` to [`Style`]s. Applications can register
+//! styles at startup (for example from a configuration file) and replace them at runtime, and code
+//! that produces output looks up styles by key instead of hard-coding them.
+
+use std::{
+    collections::BTreeMap,
+    sync::{PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard},
+};
+
+use crate::Style;
+
+/// Styles registered under their keys.
+static REGISTRY: RwLock<BTreeMap<String, Style>> = RwLock::new(BTreeMap::new());
+
+/// Acquires the registry for reading.
+///
+/// A poisoned lock is ignored, since a panic cannot leave the map of [`Copy`] values in an
+/// inconsistent state.
+#[inline]
+fn read() -> RwLockReadGuard<'static, BTreeMap<String, Style>> {
+    REGISTRY.read().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Acquires the registry for writing.
+///
+/// A poisoned lock is ignored, since a panic cannot leave the map of [`Copy`] values in an
+/// inconsistent state.
+#[inline]
+fn write() -> RwLockWriteGuard<'static, BTreeMap<String, Style>> {
+    REGISTRY.write().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Returns the style registered under the specified key, or `None` if no style is registered
+/// under that key.
+#[must_use]
+pub fn lookup(key: &str) -> Option<Style> {
+    read().get(key).copied()
+}
+
+/// Returns the style registered under the specified key, or the default style if no style is
+/// registered under that key.
+#[must_use]
+pub fn lookup_or_default(key: &str) -> Style {
+    lookup(key).unwrap_or_default()
+}
+
+/// Registers a style under the specified key and returns the style previously registered under that
+/// key, if any.
+pub fn register(key: impl Into<String>, style: Style) -> Option<Style> {
+    write().insert(key.into(), style)
+}
+
+/// Registers each style under its key, replacing previously registered styles with the same keys.
+pub fn register_all<I, K>(styles: I)
+where
+    I: IntoIterator<Item = (K, Style)>,
+    K: Into<String>,
+{
+    let mut registry = write();
+    for (key, style) in styles {
+        registry.insert(key.into(), style);
+    }
+}
+
+/// Removes the style registered under the specified key and returns it, if any.
+#[expect(clippy::must_use_candidate)]
+pub fn unregister(key: &str) -> Option<Style> {
+    write().remove(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Color;
+
+    use super::*;
+
+    #[test]
+    fn lookup_unregistered() {
+        assert_eq!(lookup("registry-test-unregistered"), None);
+        assert_eq!(
+            lookup_or_default("registry-test-unregistered"),
+            Style::default()
+        );
+    }
+
+    #[test]
+    fn register_and_lookup() {
+        let style = Style {
+            foreground_color: Color::Yellow,
+            bold: true,
+            ..Default::default()
+        };
+        assert_eq!(register("registry-test-register", style), None);
+        assert_eq!(lookup("registry-test-register"), Some(style));
+        assert_eq!(lookup_or_default("registry-test-register"), style);
+    }
+
+    #[test]
+    fn register_override() {
+        let old_style = Style {
+            foreground_color: Color::Red,
+            ..Default::default()
+        };
+        let new_style = Style {
+            foreground_color: Color::LightRed,
+            ..Default::default()
+        };
+        register("registry-test-override", old_style);
+        assert_eq!(
+            register("registry-test-override", new_style),
+            Some(old_style)
+        );
+        assert_eq!(lookup("registry-test-override"), Some(new_style));
+    }
+
+    #[test]
+    fn register_all_and_unregister() {
+        let style = Style {
+            underlined: true,
+            ..Default::default()
+        };
+        register_all([
+            ("registry-test-all-1", style),
+            ("registry-test-all-2", Style::default()),
+        ]);
+        assert_eq!(lookup("registry-test-all-1"), Some(style));
+        assert_eq!(lookup("registry-test-all-2"), Some(Style::default()));
+        assert_eq!(unregister("registry-test-all-1"), Some(style));
+        assert_eq!(lookup("registry-test-all-1"), None);
+        assert_eq!(unregister("registry-test-all-1"), None);
+    }
+}
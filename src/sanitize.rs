@@ -0,0 +1,330 @@
+//! Sanitization of control characters and escape sequences in output text.
+
+use core::fmt::{self, Display, Formatter, Write as _};
+use std::borrow::Cow;
+
+use crate::{Style, StyledDisplay};
+
+/// How control characters in output text are sanitized.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum SanitizeMode {
+    /// Control characters are replaced by their Rust escape sequence, e.g., `\u{1b}` or `\r`.
+    #[default]
+    Escape,
+    /// Control characters are removed.
+    Strip,
+}
+
+/// Displayable value whose control characters are sanitized when it is formatted.
+///
+/// The value must implement the [`Display`] trait. When `SanitizedDisplay` is formatted or
+/// converted to a string, all control characters except line feed and horizontal tab are escaped or
+/// removed as specified by [`mode`](Self::mode). This prevents untrusted text such as file names or
+/// user data from changing the terminal title, moving the cursor, or otherwise manipulating the
+/// terminal. The value can in turn be wrapped in a [`StyledDisplay`](crate::StyledDisplay), whose
+/// own control sequences are not affected.
+///
+/// Width, alignment, and fill are applied to the sanitized text, and precision truncates it.
+#[expect(clippy::exhaustive_structs)]
+pub struct SanitizedDisplay<T>
+where
+    T: Display + ?Sized,
+{
+    /// How control characters are sanitized.
+    pub mode: SanitizeMode,
+    /// The value to display with sanitized control characters.
+    pub value: T,
+}
+
+impl<T> Display for SanitizedDisplay<T>
+where
+    T: Display + ?Sized,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if f.width().is_none() && f.precision().is_none() {
+            let mut writer = SanitizingWriter {
+                inner: f,
+                mode: self.mode,
+            };
+            write!(writer, "{}", &self.value)
+        } else {
+            let mut sanitized = String::new();
+            let mut writer = SanitizingWriter {
+                inner: &mut sanitized,
+                mode: self.mode,
+            };
+            write!(writer, "{}", &self.value)?;
+            f.pad(&sanitized)
+        }
+    }
+}
+
+/// Returns the text with all control characters except line feed and horizontal tab escaped or
+/// removed as specified by `mode`.
+///
+/// The text is borrowed if it does not contain any such control characters.
+#[must_use]
+pub fn sanitize(text: &str, mode: SanitizeMode) -> Cow<'_, str> {
+    if text.chars().any(is_unsafe_control) {
+        let mut sanitized = String::with_capacity(text.len());
+        for ch in text.chars() {
+            if !is_unsafe_control(ch) {
+                sanitized.push(ch);
+            } else if mode == SanitizeMode::Escape {
+                sanitized.extend(ch.escape_default());
+            }
+        }
+        Cow::Owned(sanitized)
+    } else {
+        Cow::Borrowed(text)
+    }
+}
+
+/// Returns the text with all ANSI escape sequences removed.
+///
+/// Control sequences (e.g., `ESC [ 1 m`), control strings (e.g., `ESC ] 0 ; title BEL`), and other
+/// escape sequences are removed, including their 8-bit forms. Unterminated control strings extend
+/// to the end of the text. Other control characters are retained; use [`sanitize`] or [`restyle`]
+/// to handle them as well.
+///
+/// The text is borrowed if it does not contain any escape sequences.
+#[must_use]
+pub fn strip_ansi(text: &str) -> Cow<'_, str> {
+    if text.contains(is_sequence_start) {
+        let mut stripped = String::with_capacity(text.len());
+        let mut rest = text;
+        while let Some(pos) = rest.find(is_sequence_start) {
+            stripped.push_str(&rest[..pos]);
+            rest = skip_sequence(&rest[pos..]);
+        }
+        stripped.push_str(rest);
+        Cow::Owned(stripped)
+    } else {
+        Cow::Borrowed(text)
+    }
+}
+
+/// Prepares possibly ANSI-laden text (e.g., output of a subprocess) to be displayed in the
+/// specified style.
+///
+/// All ANSI escape sequences are removed from the text as by [`strip_ansi`], and the remaining
+/// control characters are sanitized as by [`sanitize`]. The result displays the text in `style`
+/// only, regardless of any styling that was present in the input.
+#[must_use]
+pub fn restyle(text: &str, style: Style, mode: SanitizeMode) -> StyledDisplay<Cow<'_, str>> {
+    let value = match strip_ansi(text) {
+        Cow::Borrowed(stripped) => sanitize(stripped, mode),
+        Cow::Owned(stripped) => Cow::Owned(sanitize(&stripped, mode).into_owned()),
+    };
+    StyledDisplay { style, value }
+}
+
+/// Returns whether the character starts an escape sequence, control sequence, or control string.
+#[inline]
+#[must_use]
+pub(crate) fn is_sequence_start(ch: char) -> bool {
+    matches!(
+        ch,
+        '\x1b' | '\u{90}' | '\u{98}' | '\u{9b}' | '\u{9d}' | '\u{9e}' | '\u{9f}'
+    )
+}
+
+/// Returns the remainder of the text after the escape sequence, control sequence, or control
+/// string at its start.
+#[must_use]
+pub(crate) fn skip_sequence(text: &str) -> &str {
+    // Returns the remainder of a control sequence after the Control Sequence Introducer (CSI):
+    // parameter and intermediate bytes, followed by a final byte.
+    fn skip_control_sequence(rest: &str) -> &str {
+        let rest = rest.trim_start_matches(|ch| ('\x20'..='\x3f').contains(&ch));
+        rest.strip_prefix(|ch| ('\x40'..='\x7e').contains(&ch))
+            .unwrap_or(rest)
+    }
+
+    // Returns the remainder of a control string after its opening delimiter: arbitrary characters,
+    // terminated by String Terminator (ST) or BEL.
+    fn skip_control_string(rest: &str) -> &str {
+        let Some(pos) = rest.find(['\x07', '\x1b', '\u{9c}']) else {
+            return "";
+        };
+        let rest = &rest[pos..];
+        let mut chars = rest.chars();
+        match chars.next() {
+            // An escape sequence other than ST aborts the control string.
+            Some('\x1b') => rest.strip_prefix("\x1b\\").unwrap_or(rest),
+            _ => chars.as_str(),
+        }
+    }
+
+    let mut chars = text.chars();
+    match chars.next() {
+        Some('\x1b') => {
+            let rest = chars.as_str();
+            match chars.next() {
+                Some('[') => skip_control_sequence(chars.as_str()),
+                Some(']' | 'P' | 'X' | '^' | '_') => skip_control_string(chars.as_str()),
+                Some('\x20'..='\x2f') => {
+                    let rest = rest.trim_start_matches(|ch| ('\x20'..='\x2f').contains(&ch));
+                    rest.strip_prefix(|ch| ('\x30'..='\x7e').contains(&ch))
+                        .unwrap_or(rest)
+                }
+                Some('\x30'..='\x7e') => chars.as_str(),
+                // A lone ESC is removed.
+                _ => rest,
+            }
+        }
+        Some('\u{9b}') => skip_control_sequence(chars.as_str()),
+        Some(_) => skip_control_string(chars.as_str()),
+        None => "",
+    }
+}
+
+/// Returns whether the character is a control character that must be sanitized.
+#[inline]
+#[must_use]
+fn is_unsafe_control(ch: char) -> bool {
+    ch.is_control() && ch != '\n' && ch != '\t'
+}
+
+/// [`fmt::Write`] adapter that sanitizes control characters before forwarding text to the inner
+/// writer.
+struct SanitizingWriter<'a, W>
+where
+    W: fmt::Write + ?Sized,
+{
+    /// The writer to which sanitized text is forwarded.
+    inner: &'a mut W,
+    /// How control characters are sanitized.
+    mode: SanitizeMode,
+}
+
+impl<W> fmt::Write for SanitizingWriter<'_, W>
+where
+    W: fmt::Write + ?Sized,
+{
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let mut rest = s;
+        while let Some(pos) = rest.find(is_unsafe_control) {
+            self.inner.write_str(&rest[..pos])?;
+            let mut chars = rest[pos..].chars();
+            if let Some(ch) = chars.next()
+                && self.mode == SanitizeMode::Escape
+            {
+                for escaped in ch.escape_default() {
+                    self.inner.write_char(escaped)?;
+                }
+            }
+            rest = chars.as_str();
+        }
+        self.inner.write_str(rest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Color;
+
+    use super::*;
+
+    #[test]
+    fn sanitize_clean_text() {
+        let result = sanitize("foo\tbar\nbaz", SanitizeMode::Escape);
+        assert!(matches!(result, Cow::Borrowed("foo\tbar\nbaz")));
+    }
+
+    #[test]
+    fn sanitize_escape() {
+        let result = sanitize("a\x1b]0;pwned\x07b\r\u{9b}c", SanitizeMode::Escape);
+        assert_eq!(result, "a\\u{1b}]0;pwned\\u{7}b\\r\\u{9b}c");
+    }
+
+    #[test]
+    fn sanitize_strip() {
+        let result = sanitize("a\x1b[2Jb\x08\x7fc\n", SanitizeMode::Strip);
+        assert_eq!(result, "a[2Jbc\n");
+    }
+
+    #[test]
+    fn sanitized_display() {
+        let sanitized = SanitizedDisplay {
+            mode: SanitizeMode::Escape,
+            value: "foo\x1b[1mbar",
+        };
+        let result = sanitized.to_string();
+        assert_eq!(result, "foo\\u{1b}[1mbar");
+    }
+
+    #[test]
+    fn sanitized_display_formatting() {
+        let sanitized = SanitizedDisplay {
+            mode: SanitizeMode::Strip,
+            value: "f\x1bo\x1bo",
+        };
+        let result = format!(">{sanitized:_>5}<{sanitized:.2}>");
+        assert_eq!(result, ">__foo<fo>");
+    }
+
+    #[test]
+    fn sanitized_display_in_styled_display() {
+        let styled = StyledDisplay {
+            style: Style {
+                foreground_color: Color::Yellow,
+                ..Default::default()
+            },
+            value: SanitizedDisplay {
+                mode: SanitizeMode::Strip,
+                value: "f\x1b[0mo\u{9b}o",
+            },
+        };
+        let result = styled.to_string();
+        assert_eq!(result, "\x1b[33mf[0moo\x1b[0m");
+    }
+
+    #[test]
+    fn strip_ansi_clean_text() {
+        let result = strip_ansi("foo\r\nbar");
+        assert!(matches!(result, Cow::Borrowed("foo\r\nbar")));
+    }
+
+    #[test]
+    fn strip_ansi_control_sequences() {
+        let result = strip_ansi("\x1b[1;31mfoo\x1b[0m \u{9b}2Jbar\x1b[?25l");
+        assert_eq!(result, "foo bar");
+    }
+
+    #[test]
+    fn strip_ansi_control_strings() {
+        let result =
+            strip_ansi("a\x1b]0;title\x07b\x1b]8;;https://example.com\x1b\\c\u{9d}x\u{9c}d");
+        assert_eq!(result, "abcd");
+    }
+
+    #[test]
+    fn strip_ansi_escape_sequences() {
+        let result = strip_ansi("a\x1b(Bb\x1b7c\x1b");
+        assert_eq!(result, "abc");
+    }
+
+    #[test]
+    fn strip_ansi_unterminated_control_string() {
+        let result = strip_ansi("foo\x1b]0;title");
+        assert_eq!(result, "foo");
+    }
+
+    #[test]
+    fn strip_ansi_aborted_control_string() {
+        let result = strip_ansi("foo\x1bPdata\x1b[1mbar");
+        assert_eq!(result, "foobar");
+    }
+
+    #[test]
+    fn restyle_untrusted_text() {
+        let style = Style {
+            foreground_color: Color::Red,
+            ..Default::default()
+        };
+        let result = restyle("\x1b[32mok\x1b[0m\r", style, SanitizeMode::Escape).to_string();
+        assert_eq!(result, "\x1b[31mok\\r\x1b[0m");
+    }
+}
@@ -0,0 +1,156 @@
+//! Side-by-side layout of text blocks.
+
+use core::fmt::{self, Display, Formatter};
+
+use crate::{StyledDisplay, width::display_width, wrap::wrap_line};
+
+/// Text blocks that are displayed next to each other in columns.
+///
+/// When `SideBySideDisplay` is formatted or converted to a string, the available width (i.e.,
+/// [`width`](Self::width) minus the gutters) is divided evenly among the blocks. Each block is
+/// wrapped independently to the width of its column, and the columns are separated by the
+/// [`gutter`](Self::gutter). Blocks and gutters are displayed in their styles, with the styles
+/// reset at the end of each line. The lines are separated by line feeds, and the last line is not
+/// terminated.
+#[expect(clippy::exhaustive_structs)]
+pub struct SideBySideDisplay<'a> {
+    /// The text blocks, from left to right.
+    pub blocks: &'a [StyledDisplay<&'a str>],
+    /// The text displayed between adjacent columns on each line, e.g., `" │ "`.
+    pub gutter: StyledDisplay<&'a str>,
+    /// The maximum number of columns occupied by a line.
+    pub width: usize,
+}
+
+impl Display for SideBySideDisplay<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let count = self.blocks.len();
+        if count == 0 {
+            return Ok(());
+        }
+        let gutter_width = display_width(self.gutter.value);
+        let available = self.width.saturating_sub(gutter_width * (count - 1));
+        let column_widths: Vec<usize> = (0..count)
+            .map(|index| available / count + usize::from(index < available % count))
+            .collect();
+
+        let columns: Vec<Vec<&str>> = self
+            .blocks
+            .iter()
+            .zip(&column_widths)
+            .map(|(block, &column_width)| {
+                block
+                    .value
+                    .split('\n')
+                    .flat_map(|line| wrap_line(line, column_width))
+                    .collect()
+            })
+            .collect();
+        let rows = columns.iter().map(Vec::len).max().unwrap_or(0);
+
+        for row in 0..rows {
+            if row != 0 {
+                f.write_str("\n")?;
+            }
+            for (index, ((column, block), &column_width)) in columns
+                .iter()
+                .zip(self.blocks)
+                .zip(&column_widths)
+                .enumerate()
+            {
+                let line = column.get(row).copied().unwrap_or("");
+                if index != 0 {
+                    write!(f, "{}", self.gutter)?;
+                }
+                if !line.is_empty() {
+                    let styled = StyledDisplay {
+                        style: block.style,
+                        value: line,
+                    };
+                    write!(f, "{styled}")?;
+                }
+                if index != count - 1 {
+                    let padding = column_width.saturating_sub(display_width(line));
+                    write!(f, "{:padding$}", "")?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Color, Style};
+
+    use super::*;
+
+    /// Returns the text in the default style.
+    fn plain(value: &str) -> StyledDisplay<&str> {
+        StyledDisplay {
+            style: Style::default(),
+            value,
+        }
+    }
+
+    #[test]
+    fn side_by_side_empty() {
+        let layout = SideBySideDisplay {
+            blocks: &[],
+            gutter: plain(" | "),
+            width: 80,
+        };
+        assert_eq!(layout.to_string(), "");
+    }
+
+    #[test]
+    fn side_by_side_two_blocks() {
+        let layout = SideBySideDisplay {
+            blocks: &[plain("the quick brown fox"), plain("jumps over\nthe dog")],
+            gutter: plain(" | "),
+            width: 23,
+        };
+        assert_eq!(
+            layout.to_string(),
+            "the quick  | jumps over\nbrown fox  | the dog"
+        );
+    }
+
+    #[test]
+    fn side_by_side_three_blocks() {
+        let layout = SideBySideDisplay {
+            blocks: &[plain("a"), plain("b b"), plain("c")],
+            gutter: plain("|"),
+            width: 9,
+        };
+        assert_eq!(layout.to_string(), "a  |b |c\n   |b |");
+    }
+
+    #[test]
+    fn side_by_side_styled() {
+        let red = Style {
+            foreground_color: Color::Red,
+            ..Default::default()
+        };
+        let dim = Style {
+            foreground_color: Color::DarkGray,
+            ..Default::default()
+        };
+        let layout = SideBySideDisplay {
+            blocks: &[
+                StyledDisplay {
+                    style: red,
+                    value: "old",
+                },
+                plain("new"),
+            ],
+            gutter: StyledDisplay {
+                style: dim,
+                value: "│",
+            },
+            width: 9,
+        };
+        assert_eq!(layout.to_string(), "\x1b[31mold\x1b[0m \x1b[90m│\x1b[0mnew");
+    }
+}
@@ -0,0 +1,347 @@
+//! Source code snippets with labeled spans.
+
+use core::{
+    fmt::{self, Display, Formatter},
+    ops::Range,
+};
+use std::borrow::Cow;
+
+use crate::{Highlighter, Style, StyledDisplay, width::char_width};
+
+/// Number of columns between tab stops when tabs in source code are expanded.
+///
+/// This is intentionally narrower than the eight columns between the tab stops of a terminal,
+/// which [`ColumnTrackingWriter`](crate::ColumnTrackingWriter) assumes: source code is
+/// conventionally indented by four columns per tab, and snippets are always displayed with their
+/// tabs expanded, so the terminal's tab stops do not apply to them.
+const TAB_WIDTH: usize = 4;
+
+/// Labeled byte range of a source code snippet.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[expect(clippy::exhaustive_structs)]
+pub struct SnippetLabel<'a> {
+    /// The byte range in the source code. An empty range marks a single position.
+    pub range: Range<usize>,
+    /// The label text, displayed after the markers on the last line of the range. It may be empty.
+    pub text: &'a str,
+    /// The style of the markers and the label text.
+    pub style: Style,
+}
+
+/// Source code snippet with line numbers and labeled spans, similar to compiler diagnostics.
+///
+/// When `SnippetDisplay` is formatted or converted to a string, each line of the source code is
+/// displayed after a gutter containing its line number. Each label that overlaps a line is marked
+/// by carets (`^`) on a separate line beneath it, and the label text follows the carets on the last
+/// line of its range. Tabs are expanded to multiples of four columns, and wide characters are
+/// marked by as many carets as they occupy columns. If a [`highlighter`](Self::highlighter) is
+/// specified, the source code is displayed in the styles it assigns. The lines are separated by
+/// line feeds, and the last line is not terminated.
+///
+/// ```text
+/// 3 | let x = foo(bar);
+///   |         ^^^ not found in this scope
+/// ```
+#[derive(Clone)]
+#[expect(clippy::exhaustive_structs)]
+pub struct SnippetDisplay<'a> {
+    /// The source code, consisting of one or more lines.
+    pub source: &'a str,
+    /// The line number of the first line of the source code.
+    pub first_line_number: usize,
+    /// The labeled byte ranges in the source code.
+    pub labels: &'a [SnippetLabel<'a>],
+    /// The style of the gutter.
+    pub gutter_style: Style,
+    /// The syntax highlighter applied to the source code, if any.
+    pub highlighter: Option<&'a dyn Highlighter>,
+}
+
+impl Display for SnippetDisplay<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let source = self.source.strip_suffix('\n').unwrap_or(self.source);
+        let last_line_number = self.first_line_number + source.matches('\n').count();
+        let number_width = last_line_number.to_string().len();
+        let empty_gutter = StyledDisplay {
+            style: self.gutter_style,
+            value: format_args!("{:number_width$} |", ""),
+        };
+
+        let spans = self
+            .highlighter
+            .map(|highlighter| highlighter.highlight(source))
+            .unwrap_or_default();
+
+        let mut line_start = 0;
+        for (index, line) in source.split('\n').enumerate() {
+            let line_end = line_start + line.len();
+            let line = line.strip_suffix('\r').unwrap_or(line);
+            if index != 0 {
+                f.write_str("\n")?;
+            }
+
+            let line_number = self.first_line_number + index;
+            let gutter = StyledDisplay {
+                style: self.gutter_style,
+                value: format_args!("{line_number:>number_width$} |"),
+            };
+            write!(f, "{gutter}")?;
+            if !line.is_empty() {
+                f.write_str(" ")?;
+                write_highlighted(f, line, line_start, &spans)?;
+            }
+
+            for label in self.labels {
+                // A label overlaps the line if it starts on the line, or if it starts on a previous
+                // line and ends after the start of this line.
+                let starts_on_line = label.range.start >= line_start
+                    && (label.range.start < line_end
+                        || label.range.start == line_end && label.range.is_empty());
+                let continues_on_line =
+                    label.range.start < line_start && label.range.end > line_start;
+                if !starts_on_line && !continues_on_line {
+                    continue;
+                }
+
+                let start_column = column(line, label.range.start.saturating_sub(line_start));
+                // A reversed range is treated as a single position.
+                let end = label.range.end.max(label.range.start).min(line_end) - line_start;
+                let end_column = column(line, end).max(start_column + 1);
+                let marker = StyledDisplay {
+                    style: label.style,
+                    value: format_args!("{:^<1$}", "", end_column - start_column),
+                };
+                write!(f, "\n{empty_gutter} {:start_column$}{marker}", "")?;
+                if label.range.end <= line_end && !label.text.is_empty() {
+                    let text = StyledDisplay {
+                        style: label.style,
+                        value: label.text,
+                    };
+                    write!(f, " {text}")?;
+                }
+            }
+
+            line_start = line_end + 1;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the column at which the character at the specified byte index of the line is displayed,
+/// with tabs expanded.
+#[must_use]
+fn column(line: &str, index: usize) -> usize {
+    line.char_indices()
+        .take_while(|&(char_index, _)| char_index < index)
+        .fold(0, |column, (_, ch)| {
+            if ch == '\t' {
+                (column / TAB_WIDTH + 1) * TAB_WIDTH
+            } else {
+                column + char_width(ch)
+            }
+        })
+}
+
+/// Writes the line with tabs expanded, in the styles of the highlighted spans that overlap it.
+///
+/// The spans are byte ranges in the source code, and `line_start` is the byte index of the start of
+/// the line in the source code.
+fn write_highlighted(
+    f: &mut Formatter<'_>,
+    line: &str,
+    line_start: usize,
+    spans: &[(Range<usize>, Style)],
+) -> fmt::Result {
+    let line_end = line_start + line.len();
+    let mut written = 0;
+    for (range, style) in spans {
+        if range.end <= line_start || range.start >= line_end {
+            continue;
+        }
+        let start = range.start.saturating_sub(line_start).max(written);
+        let end = range.end.min(line_end) - line_start;
+        let (Some(before), Some(text)) = (line.get(written..start), line.get(start..end)) else {
+            continue;
+        };
+        f.write_str(&expand_tabs(before, column(line, written)))?;
+        let styled = StyledDisplay {
+            style: *style,
+            value: expand_tabs(text, column(line, start)),
+        };
+        write!(f, "{styled}")?;
+        written = end;
+    }
+    f.write_str(&expand_tabs(&line[written..], column(line, written)))
+}
+
+/// Returns the text with tabs expanded, assuming that it starts at the specified column. The text
+/// is borrowed if it does not contain tabs.
+#[must_use]
+fn expand_tabs(text: &str, start_column: usize) -> Cow<'_, str> {
+    if !text.contains('\t') {
+        return Cow::Borrowed(text);
+    }
+    let mut expanded = String::with_capacity(text.len() + TAB_WIDTH);
+    let mut current_column = start_column;
+    for ch in text.chars() {
+        if ch == '\t' {
+            let next_column = (current_column / TAB_WIDTH + 1) * TAB_WIDTH;
+            expanded.extend(core::iter::repeat_n(' ', next_column - current_column));
+            current_column = next_column;
+        } else {
+            expanded.push(ch);
+            current_column += char_width(ch);
+        }
+    }
+    Cow::Owned(expanded)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Color;
+
+    use super::*;
+
+    /// Returns a label in the default style.
+    fn label(range: Range<usize>, text: &str) -> SnippetLabel<'_> {
+        SnippetLabel {
+            range,
+            text,
+            style: Style::default(),
+        }
+    }
+
+    #[test]
+    fn snippet_single_label() {
+        let snippet = SnippetDisplay {
+            source: "let x = foo(bar);\n",
+            first_line_number: 3,
+            labels: &[label(8..11, "not found in this scope")],
+            gutter_style: Style::default(),
+            highlighter: None,
+        };
+        assert_eq!(
+            snippet.to_string(),
+            "3 | let x = foo(bar);\n  |         ^^^ not found in this scope"
+        );
+    }
+
+    #[test]
+    fn snippet_multiple_lines() {
+        let source = "fn main() {\n    run(1, 2);\n}";
+        let snippet = SnippetDisplay {
+            source,
+            first_line_number: 9,
+            labels: &[label(16..19, "expected 3 arguments"), label(25..25, "")],
+            gutter_style: Style::default(),
+            highlighter: None,
+        };
+        assert_eq!(
+            snippet.to_string(),
+            " 9 | fn main() {\n10 |     run(1, 2);\n   |     ^^^ expected 3 arguments\n   \
+             |              ^\n11 | }"
+        );
+    }
+
+    #[test]
+    fn snippet_multi_line_label() {
+        let snippet = SnippetDisplay {
+            source: "a(\n  b)",
+            first_line_number: 1,
+            labels: &[label(0..7, "call")],
+            gutter_style: Style::default(),
+            highlighter: None,
+        };
+        assert_eq!(
+            snippet.to_string(),
+            "1 | a(\n  | ^^\n2 |   b)\n  | ^^^^ call"
+        );
+    }
+
+    #[test]
+    fn snippet_reversed_range() {
+        let snippet = SnippetDisplay {
+            source: "ab\ncdefg",
+            first_line_number: 1,
+            labels: &[label(Range { start: 5, end: 2 }, "here")],
+            gutter_style: Style::default(),
+            highlighter: None,
+        };
+        assert_eq!(snippet.to_string(), "1 | ab\n2 | cdefg\n  |   ^ here");
+    }
+
+    #[test]
+    fn snippet_tabs_and_wide_characters() {
+        let source = "\tx = \"日本\" + y";
+        let snippet = SnippetDisplay {
+            source,
+            first_line_number: 1,
+            labels: &[label(6..12, ""), label(16..17, "here")],
+            gutter_style: Style::default(),
+            highlighter: None,
+        };
+        assert_eq!(
+            snippet.to_string(),
+            "1 |     x = \"日本\" + y\n  |          ^^^^\n  |                  ^ here"
+        );
+    }
+
+    #[test]
+    fn snippet_styled() {
+        let blue = Style {
+            foreground_color: Color::Blue,
+            ..Default::default()
+        };
+        let red = Style {
+            foreground_color: Color::Red,
+            ..Default::default()
+        };
+        let snippet = SnippetDisplay {
+            source: "foo",
+            first_line_number: 1,
+            labels: &[SnippetLabel {
+                range: 0..3,
+                text: "bad",
+                style: red,
+            }],
+            gutter_style: blue,
+            highlighter: None,
+        };
+        assert_eq!(
+            snippet.to_string(),
+            "\x1b[34m1 |\x1b[0m foo\n\x1b[34m  |\x1b[0m \x1b[31m^^^\x1b[0m \x1b[31mbad\x1b[0m"
+        );
+    }
+
+    /// Highlighter that styles every occurrence of a keyword.
+    struct KeywordHighlighter(&'static str, Style);
+
+    impl Highlighter for KeywordHighlighter {
+        fn highlight(&self, source: &str) -> Vec<(Range<usize>, Style)> {
+            source
+                .match_indices(self.0)
+                .map(|(index, keyword)| (index..index + keyword.len(), self.1))
+                .collect()
+        }
+    }
+
+    #[test]
+    fn snippet_highlighted() {
+        let magenta = Style {
+            foreground_color: Color::Magena,
+            ..Default::default()
+        };
+        let highlighter = KeywordHighlighter("let", magenta);
+        let snippet = SnippetDisplay {
+            source: "let x;\n\tlet y;",
+            first_line_number: 1,
+            labels: &[label(12..13, "")],
+            gutter_style: Style::default(),
+            highlighter: Some(&highlighter),
+        };
+        assert_eq!(
+            snippet.to_string(),
+            "1 | \x1b[35mlet\x1b[0m x;\n2 |     \x1b[35mlet\x1b[0m y;\n  |         ^"
+        );
+    }
+}
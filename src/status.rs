@@ -0,0 +1,217 @@
+//! Status messages with icons, e.g., `✓ done`.
+
+use core::fmt::{self, Display, Formatter};
+
+use crate::{Color, GlyphSet, Style, StyledDisplay, registry, width::display_width};
+
+/// Kind of a status message.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum StatusKind {
+    /// An operation succeeded.
+    Success,
+    /// An operation succeeded, but something may need attention.
+    Warning,
+    /// An operation failed.
+    Failure,
+}
+
+impl StatusKind {
+    /// Returns the key under which the style of the kind can be [registered](registry::register) to
+    /// override the default style, e.g., `"status-success"`.
+    ///
+    /// The keys are prefixed with `status-`, so that they differ from the
+    /// [labels](crate::DiagnosticLevel::label) of diagnostic levels, e.g., `"warning"`.
+    #[inline]
+    #[must_use]
+    pub const fn key(self) -> &'static str {
+        match self {
+            Self::Success => "status-success",
+            Self::Warning => "status-warning",
+            Self::Failure => "status-failure",
+        }
+    }
+
+    /// Returns the icon of the kind from the glyph set, e.g., `✓` for [`Success`](Self::Success).
+    #[inline]
+    #[must_use]
+    pub const fn icon(self, glyphs: &GlyphSet) -> &'static str {
+        match self {
+            Self::Success => glyphs.check_mark,
+            Self::Warning => glyphs.exclamation_mark,
+            Self::Failure => glyphs.cross_mark,
+        }
+    }
+
+    /// Returns the style of the kind's icon.
+    ///
+    /// This is the style registered under the [key](Self::key) if there is one, and a bold,
+    /// kind-specific color otherwise.
+    #[inline]
+    #[must_use]
+    pub fn style(self) -> Style {
+        self.style_from(registry::lookup)
+    }
+
+    /// Returns the style of the kind's icon, with registered styles looked up by the specified
+    /// function.
+    #[must_use]
+    fn style_from<F>(self, lookup: F) -> Style
+    where
+        F: FnOnce(&str) -> Option<Style>,
+    {
+        lookup(self.key()).unwrap_or_else(|| Style {
+            foreground_color: match self {
+                Self::Success => Color::LightGreen,
+                Self::Warning => Color::LightYellow,
+                Self::Failure => Color::LightRed,
+            },
+            bold: true,
+            ..Default::default()
+        })
+    }
+
+    /// Returns whether messages of the kind are conventionally written to the standard error
+    /// stream rather than the standard output stream.
+    #[inline]
+    #[must_use]
+    pub const fn is_diagnostic(self) -> bool {
+        !matches!(self, Self::Success)
+    }
+}
+
+/// Status message preceded by an icon, e.g., `✓ done`.
+///
+/// When `StatusDisplay` is formatted or converted to a string, the [icon](StatusKind::icon) of the
+/// kind is displayed in the kind's [style](StatusKind::style), followed by a space and the message.
+/// Further lines of messages that contain line feeds are indented to align with the start of the
+/// message. The lines are separated by line feeds, and the last line is not terminated.
+#[derive(Clone, Copy, Debug)]
+#[expect(clippy::exhaustive_structs)]
+pub struct StatusDisplay<'a> {
+    /// The kind of the message.
+    pub kind: StatusKind,
+    /// The message.
+    pub message: &'a str,
+    /// The glyph set containing the icon.
+    pub glyphs: &'a GlyphSet,
+}
+
+impl<'a> StatusDisplay<'a> {
+    /// Returns a success message with the icon from the [detected](GlyphSet::detect) glyph set.
+    #[inline]
+    #[must_use]
+    pub fn success(message: &'a str) -> Self {
+        Self::with_detected_glyphs(StatusKind::Success, message)
+    }
+
+    /// Returns a warning message with the icon from the [detected](GlyphSet::detect) glyph set.
+    #[inline]
+    #[must_use]
+    pub fn warning(message: &'a str) -> Self {
+        Self::with_detected_glyphs(StatusKind::Warning, message)
+    }
+
+    /// Returns a failure message with the icon from the [detected](GlyphSet::detect) glyph set.
+    #[inline]
+    #[must_use]
+    pub fn failure(message: &'a str) -> Self {
+        Self::with_detected_glyphs(StatusKind::Failure, message)
+    }
+
+    /// Returns a message of the specified kind with the icon from the detected glyph set.
+    #[must_use]
+    fn with_detected_glyphs(kind: StatusKind, message: &'a str) -> Self {
+        Self {
+            kind,
+            message,
+            glyphs: GlyphSet::detect(),
+        }
+    }
+}
+
+impl Display for StatusDisplay<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let icon = self.kind.icon(self.glyphs);
+        let styled_icon = StyledDisplay {
+            style: self.kind.style(),
+            value: icon,
+        };
+        write!(f, "{styled_icon}")?;
+
+        let indent = display_width(icon) + 1;
+        for (index, line) in self.message.split('\n').enumerate() {
+            if line.is_empty() {
+                if index != 0 {
+                    f.write_str("\n")?;
+                }
+            } else if index == 0 {
+                write!(f, " {line}")?;
+            } else {
+                write!(f, "\n{:indent$}{line}", "")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn status_success() {
+        let status = StatusDisplay {
+            kind: StatusKind::Success,
+            message: "done",
+            glyphs: &GlyphSet::UNICODE,
+        };
+        assert_eq!(status.to_string(), "\x1b[92;1m✓\x1b[0m done");
+    }
+
+    #[test]
+    fn status_ascii_multi_line() {
+        let status = StatusDisplay {
+            kind: StatusKind::Failure,
+            message: "build failed\n\nsee log",
+            glyphs: &GlyphSet::ASCII,
+        };
+        assert_eq!(
+            status.to_string(),
+            "\x1b[91;1mx\x1b[0m build failed\n\n  see log"
+        );
+    }
+
+    #[test]
+    fn status_kinds() {
+        let kinds = [
+            StatusKind::Success,
+            StatusKind::Warning,
+            StatusKind::Failure,
+        ];
+        assert_eq!(
+            kinds.map(StatusKind::key),
+            ["status-success", "status-warning", "status-failure"]
+        );
+        assert_eq!(kinds.map(StatusKind::is_diagnostic), [false, true, true]);
+        assert_eq!(StatusDisplay::warning("careful").kind, StatusKind::Warning);
+    }
+
+    #[test]
+    fn status_style_lookup() {
+        let style = Style {
+            underlined: true,
+            ..Default::default()
+        };
+        let lookup = |key: &str| (key == "status-warning").then_some(style);
+        assert_eq!(StatusKind::Warning.style_from(lookup), style);
+        assert_eq!(
+            StatusKind::Failure.style_from(lookup),
+            Style {
+                foreground_color: Color::LightRed,
+                bold: true,
+                ..Default::default()
+            }
+        );
+    }
+}
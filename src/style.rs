@@ -2,6 +2,8 @@
 
 use core::{mem::MaybeUninit, slice};
 
+use crate::terminal::ColorDepth;
+
 /// ANSI control sequence that resets all styling.
 pub(crate) const RESET_STYLE: &str = "\x1b[0m";
 
@@ -65,6 +67,102 @@ pub enum Color {
 }
 
 impl Color {
+    /// The colors of the 16-color palette, in the order of their ANSI color numbers.
+    pub(crate) const PALETTE: [Self; 16] = [
+        Self::Black,
+        Self::Red,
+        Self::Green,
+        Self::Yellow,
+        Self::Blue,
+        Self::Magena,
+        Self::Cyan,
+        Self::LightGray,
+        Self::DarkGray,
+        Self::LightRed,
+        Self::LightGreen,
+        Self::LightYellow,
+        Self::LightBlue,
+        Self::LightMagenta,
+        Self::LightCyan,
+        Self::White,
+    ];
+
+    /// Returns the color of the 16-color palette that is perceptually nearest to the specified red,
+    /// green, and blue components.
+    ///
+    /// The distance between colors is measured in the CIELAB color space, which approximates
+    /// human perception much better than the distance between the components. The colors of the
+    /// palette are assumed to be the [xterm colors](Self::rgb). This is useful to downgrade
+    /// truecolor themes for terminals with limited color support.
+    #[must_use]
+    pub fn nearest(rgb: [u8; 3]) -> Self {
+        let target = lab(rgb);
+        let distance_to = |color: &Self| {
+            color
+                .rgb()
+                .map_or(f64::MAX, |palette_rgb| distance(target, palette_rgb))
+        };
+        Self::PALETTE
+            .into_iter()
+            .min_by(|a, b| distance_to(a).total_cmp(&distance_to(b)))
+            .unwrap_or_default()
+    }
+
+    /// Returns the color of the 16-color palette that is perceptually [nearest](Self::nearest) to
+    /// the specified color of the xterm 256-color palette.
+    ///
+    /// The colors 0 to 15 are the colors of the 16-color palette in the order of their ANSI color
+    /// numbers, and are returned unchanged.
+    #[must_use]
+    pub fn from_ansi_256(index: u8) -> Self {
+        // Returns the component of the 6×6×6 color cube with the specified level.
+        const fn cube_component(level: u8) -> u8 {
+            if level == 0 { 0 } else { 55 + 40 * level }
+        }
+
+        match index {
+            0..=15 => Self::PALETTE[usize::from(index)],
+            16..=231 => {
+                let cube = index - 16;
+                Self::nearest([
+                    cube_component(cube / 36),
+                    cube_component(cube / 6 % 6),
+                    cube_component(cube % 6),
+                ])
+            }
+            232..=255 => {
+                let gray = 8 + 10 * (index - 232);
+                Self::nearest([gray, gray, gray])
+            }
+        }
+    }
+
+    /// Returns the red, green, and blue components of the color in the xterm palette, or `None` if
+    /// the color is the default color.
+    #[inline]
+    #[must_use]
+    pub const fn rgb(self) -> Option<[u8; 3]> {
+        match self {
+            Self::Default => None,
+            Self::Black => Some([0, 0, 0]),
+            Self::Red => Some([205, 0, 0]),
+            Self::Green => Some([0, 205, 0]),
+            Self::Yellow => Some([205, 205, 0]),
+            Self::Blue => Some([0, 0, 238]),
+            Self::Magena => Some([205, 0, 205]),
+            Self::Cyan => Some([0, 205, 205]),
+            Self::LightGray => Some([229, 229, 229]),
+            Self::DarkGray => Some([127, 127, 127]),
+            Self::LightRed => Some([255, 0, 0]),
+            Self::LightGreen => Some([0, 255, 0]),
+            Self::LightYellow => Some([255, 255, 0]),
+            Self::LightBlue => Some([92, 92, 255]),
+            Self::LightMagenta => Some([255, 0, 255]),
+            Self::LightCyan => Some([0, 255, 255]),
+            Self::White => Some([255, 255, 255]),
+        }
+    }
+
     /// Returns the ANSI color code if the color is used for the foreground.
     #[inline]
     #[must_use]
@@ -90,6 +188,58 @@ impl Color {
         }
     }
 
+    /// Returns the corresponding color of the basic 8-color palette if the color is a bright
+    /// color, or `None` otherwise.
+    #[inline]
+    #[must_use]
+    const fn base_color(self) -> Option<Self> {
+        match self {
+            Self::DarkGray => Some(Self::Black),
+            Self::LightRed => Some(Self::Red),
+            Self::LightGreen => Some(Self::Green),
+            Self::LightYellow => Some(Self::Yellow),
+            Self::LightBlue => Some(Self::Blue),
+            Self::LightMagenta => Some(Self::Magena),
+            Self::LightCyan => Some(Self::Cyan),
+            Self::White => Some(Self::LightGray),
+            Self::Default
+            | Self::Black
+            | Self::Red
+            | Self::Green
+            | Self::Yellow
+            | Self::Blue
+            | Self::Magena
+            | Self::Cyan
+            | Self::LightGray => None,
+        }
+    }
+
+    /// Returns whether the color is light, i.e., whether dark text is more readable on it than
+    /// light text, assuming a typical terminal palette.
+    #[inline]
+    #[must_use]
+    const fn is_light(self) -> bool {
+        match self {
+            Self::Green
+            | Self::Yellow
+            | Self::Cyan
+            | Self::LightGray
+            | Self::LightRed
+            | Self::LightGreen
+            | Self::LightYellow
+            | Self::LightMagenta
+            | Self::LightCyan
+            | Self::White => true,
+            Self::Default
+            | Self::Black
+            | Self::Red
+            | Self::Blue
+            | Self::Magena
+            | Self::DarkGray
+            | Self::LightBlue => false,
+        }
+    }
+
     /// Returns the ANSI color code if the color is used for the background.
     #[inline]
     #[must_use]
@@ -116,6 +266,57 @@ impl Color {
     }
 }
 
+/// Vertical position of text relative to the baseline.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum ScriptPosition {
+    /// Superscript (SGR code 73).
+    Superscript,
+    /// Subscript (SGR code 74).
+    Subscript,
+}
+
+/// Returns the CIELAB coordinates of the sRGB color with the specified components, relative to the
+/// D65 white point.
+#[must_use]
+fn lab(rgb: [u8; 3]) -> [f64; 3] {
+    // Returns the linear intensity of an sRGB component.
+    fn linear(component: u8) -> f64 {
+        let value = f64::from(component) / 255.0;
+        if value <= 0.040_45 {
+            value / 12.92
+        } else {
+            ((value + 0.055) / 1.055).powf(2.4)
+        }
+    }
+
+    // Returns the CIELAB transfer function applied to a tristimulus value relative to white.
+    fn transfer(value: f64) -> f64 {
+        if value > 216.0 / 24_389.0 {
+            value.cbrt()
+        } else {
+            (24_389.0 / 27.0 * value + 16.0) / 116.0
+        }
+    }
+
+    let [red, green, blue] = rgb.map(linear);
+    let x = transfer((0.412_456_4 * red + 0.357_576_1 * green + 0.180_437_5 * blue) / 0.950_47);
+    let y = transfer(0.212_672_9 * red + 0.715_152_2 * green + 0.072_175_0 * blue);
+    let z = transfer((0.019_333_9 * red + 0.119_192_0 * green + 0.950_304_1 * blue) / 1.088_83);
+    [116.0 * y - 16.0, 500.0 * (x - y), 200.0 * (y - z)]
+}
+
+/// Returns the squared distance in the CIELAB color space between the color with the specified
+/// CIELAB coordinates and the sRGB color with the specified components.
+#[must_use]
+fn distance(lab_a: [f64; 3], rgb_b: [u8; 3]) -> f64 {
+    lab_a
+        .into_iter()
+        .zip(lab(rgb_b))
+        .map(|(a, b)| (a - b).powi(2))
+        .sum()
+}
+
 /// Text color and attributes.
 #[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
 #[expect(clippy::exhaustive_structs)]
@@ -130,23 +331,320 @@ pub struct Style {
     pub underlined: bool,
     /// Blinking text.
     pub blinking: bool,
+    /// Italic text.
+    pub italic: bool,
+    /// Struck-through text.
+    pub strikethrough: bool,
+    /// Dim (faint) text.
+    pub dim: bool,
+    /// Text with foreground and background colors swapped.
+    pub reverse: bool,
+    /// Alternate font, or `None` to leave the font unchanged.
+    ///
+    /// `Some(0)` selects the primary font, and `Some(1)` to `Some(9)` select the alternate fonts
+    /// (SGR codes 11 to 19). Greater numbers are ignored. Only few terminals support font
+    /// selection; most ignore these codes.
+    pub font: Option<u8>,
+    /// Superscript or subscript text, or `None` for text on the baseline.
+    ///
+    /// Only few terminals (e.g., mintty and kitty) support superscript and subscript; most display
+    /// such text on the baseline.
+    pub script_position: Option<ScriptPosition>,
 }
 
 impl Style {
+    /// Returns the default style, which uses the terminal's default colors and no attributes.
+    ///
+    /// Unlike [`Style::default`], this can be used in constant expressions. Together with the
+    /// builder methods (e.g., [`fg`](Self::fg) and [`bold`](Self::bold())), this allows styles to
+    /// be declared as constants, e.g., `const ERROR: Style = Style::new().fg(Color::Red).bold();`.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            foreground_color: Color::Default,
+            background_color: Color::Default,
+            bold: false,
+            underlined: false,
+            blinking: false,
+            italic: false,
+            strikethrough: false,
+            dim: false,
+            reverse: false,
+            font: None,
+            script_position: None,
+        }
+    }
+
+    /// Returns this style with the specified foreground color.
+    #[inline]
+    #[must_use]
+    pub const fn fg(mut self, color: Color) -> Self {
+        self.foreground_color = color;
+        self
+    }
+
+    /// Returns this style with the specified background color.
+    #[inline]
+    #[must_use]
+    pub const fn bg(mut self, color: Color) -> Self {
+        self.background_color = color;
+        self
+    }
+
+    /// Returns this style with bold text.
+    #[inline]
+    #[must_use]
+    pub const fn bold(mut self) -> Self {
+        self.bold = true;
+        self
+    }
+
+    /// Returns this style with underlined text.
+    #[inline]
+    #[must_use]
+    pub const fn underlined(mut self) -> Self {
+        self.underlined = true;
+        self
+    }
+
+    /// Returns this style with blinking text.
+    #[inline]
+    #[must_use]
+    pub const fn blinking(mut self) -> Self {
+        self.blinking = true;
+        self
+    }
+
+    /// Returns this style with italic text.
+    #[inline]
+    #[must_use]
+    pub const fn italic(mut self) -> Self {
+        self.italic = true;
+        self
+    }
+
+    /// Returns this style with struck-through text.
+    #[inline]
+    #[must_use]
+    pub const fn strikethrough(mut self) -> Self {
+        self.strikethrough = true;
+        self
+    }
+
+    /// Returns this style with dim (faint) text.
+    #[inline]
+    #[must_use]
+    pub const fn dim(mut self) -> Self {
+        self.dim = true;
+        self
+    }
+
+    /// Returns this style with foreground and background colors swapped.
+    #[inline]
+    #[must_use]
+    pub const fn reverse(mut self) -> Self {
+        self.reverse = true;
+        self
+    }
+
+    /// Returns this style with the specified [font](Self::font).
+    #[inline]
+    #[must_use]
+    pub const fn with_font(mut self, font: u8) -> Self {
+        self.font = Some(font);
+        self
+    }
+
+    /// Returns this style with the specified [script position](Self::script_position).
+    #[inline]
+    #[must_use]
+    pub const fn with_script_position(mut self, position: ScriptPosition) -> Self {
+        self.script_position = Some(position);
+        self
+    }
+
+    /// Returns this style with bright colors replaced by colors of the basic 8-color palette.
+    ///
+    /// Terminals that only support 8 colors do not support the control sequences for bright
+    /// colors, and may display such colors in the default color. This function provides a fallback
+    /// for such terminals: A bright foreground color is replaced by the corresponding basic color,
+    /// and the text is made bold, which these terminals traditionally display in the bright
+    /// variant of the color. A bright background color is replaced by the corresponding basic
+    /// color.
+    #[inline]
+    #[must_use]
+    pub const fn with_bright_as_bold(self) -> Self {
+        let mut style = self;
+        if let Some(color) = self.foreground_color.base_color() {
+            style.foreground_color = color;
+            style.bold = true;
+        }
+        if let Some(color) = self.background_color.base_color() {
+            style.background_color = color;
+        }
+        style
+    }
+
+    /// Returns this style downgraded to the colors supported by a terminal with the specified
+    /// color depth.
+    ///
+    /// With [`ColorDepth::NoColor`], the colors are replaced by the default colors and the
+    /// attributes are retained. With any other color depth, the style is returned unchanged:
+    /// [`Color`] only represents the default colors and the 16-color palette, which every color
+    /// depth except `NoColor` supports, so there is nothing to downgrade. Colors of the 256-color
+    /// palette and RGB colors are mapped to the palette by [`Color::from_ansi_256`] and
+    /// [`Color::nearest`] before they can be used in a `Style`.
+    #[inline]
+    #[must_use]
+    pub const fn with_color_depth(self, depth: ColorDepth) -> Self {
+        let mut style = self;
+        if matches!(depth, ColorDepth::NoColor) {
+            style.foreground_color = Color::Default;
+            style.background_color = Color::Default;
+        }
+        style
+    }
+
+    /// Returns whether text in this style is likely readable, i.e., whether the foreground and
+    /// background colors are likely distinguishable.
+    ///
+    /// The colors are considered indistinguishable if they are the same non-default color, or if
+    /// one is the bright variant of the other (e.g., [`Red`](Color::Red) and
+    /// [`LightRed`](Color::LightRed), or [`Black`](Color::Black) and
+    /// [`DarkGray`](Color::DarkGray)), since many terminal palettes render such pairs with little
+    /// contrast. If either color is the default color, the style is considered readable, because
+    /// the terminal's default colors are unknown.
+    #[inline]
+    #[must_use]
+    pub const fn has_readable_colors(self) -> bool {
+        // Returns the color with bright variants replaced by the corresponding basic color.
+        const fn family(color: Color) -> Color {
+            match color.base_color() {
+                Some(base) => base,
+                None => color,
+            }
+        }
+
+        matches!(self.foreground_color, Color::Default)
+            || matches!(self.background_color, Color::Default)
+            || family(self.foreground_color) as u8 != family(self.background_color) as u8
+    }
+
+    /// Returns this style, with the foreground color replaced by a contrasting color if the style
+    /// does not have [readable colors](Self::has_readable_colors).
+    ///
+    /// The contrasting color is [`Black`](Color::Black) on light backgrounds and
+    /// [`White`](Color::White) on dark backgrounds. This prevents invisible text if, for example,
+    /// user-configured styles collide.
+    #[inline]
+    #[must_use]
+    pub const fn with_readable_colors(self) -> Self {
+        let mut style = self;
+        if !self.has_readable_colors() {
+            style.foreground_color = if self.background_color.is_light() {
+                Color::Black
+            } else {
+                Color::White
+            };
+        }
+        style
+    }
+
+    /// Returns this style modified by the parameters of a Select Graphic Rendition (SGR) control
+    /// sequence, e.g., `"1;31"`.
+    ///
+    /// Empty parameters are treated as `0`, which resets the style. Colors of the 256-color palette
+    /// and RGB colors are replaced by the [nearest](Color::nearest) color of the 16-color palette.
+    /// Parameters that are malformed or set attributes not supported by `Style` are ignored. Since
+    /// selecting the primary font (SGR code 10) restores the terminal's default font, it resets the
+    /// [font](Self::font) to `None`.
+    #[must_use]
+    pub(crate) fn with_sgr_parameters(self, parameters: &str) -> Self {
+        let mut style = self;
+        let mut parameters = parameters.split([';', ':']).map(|parameter| {
+            if parameter.is_empty() {
+                Some(0)
+            } else {
+                parameter.parse::<usize>().ok()
+            }
+        });
+        while let Some(parameter) = parameters.next() {
+            let Some(parameter) = parameter else {
+                continue;
+            };
+            match parameter {
+                0 => style = Self::default(),
+                1 => style.bold = true,
+                2 => style.dim = true,
+                3 => style.italic = true,
+                4 => style.underlined = true,
+                5 | 6 => style.blinking = true,
+                7 => style.reverse = true,
+                9 => style.strikethrough = true,
+                22 => {
+                    style.bold = false;
+                    style.dim = false;
+                }
+                23 => style.italic = false,
+                24 => style.underlined = false,
+                25 => style.blinking = false,
+                27 => style.reverse = false,
+                29 => style.strikethrough = false,
+                10 => style.font = None,
+                11..=19 => style.font = u8::try_from(parameter - 10).ok(),
+                73 => style.script_position = Some(ScriptPosition::Superscript),
+                74 => style.script_position = Some(ScriptPosition::Subscript),
+                75 => style.script_position = None,
+                30..=37 => style.foreground_color = Color::PALETTE[parameter - 30],
+                39 => style.foreground_color = Color::Default,
+                40..=47 => style.background_color = Color::PALETTE[parameter - 40],
+                49 => style.background_color = Color::Default,
+                90..=97 => style.foreground_color = Color::PALETTE[parameter - 90 + 8],
+                100..=107 => style.background_color = Color::PALETTE[parameter - 100 + 8],
+                38 | 48 => {
+                    let mut component = || parameters.next().flatten().map(u8::try_from);
+                    let color = match component() {
+                        Some(Ok(5)) => match component() {
+                            Some(Ok(index)) => Some(Color::from_ansi_256(index)),
+                            _ => None,
+                        },
+                        Some(Ok(2)) => match (component(), component(), component()) {
+                            (Some(Ok(red)), Some(Ok(green)), Some(Ok(blue))) => {
+                                Some(Color::nearest([red, green, blue]))
+                            }
+                            _ => None,
+                        },
+                        _ => None,
+                    };
+                    if let Some(color) = color {
+                        if parameter == 38 {
+                            style.foreground_color = color;
+                        } else {
+                            style.background_color = color;
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        style
+    }
+
     /// Creates a buffer to be passed to the [`set_style`](Self::set_style) function.
     #[inline]
     #[must_use]
-    pub(crate) fn new_set_style_buffer() -> [MaybeUninit<u8>; 15] {
-        [const { MaybeUninit::uninit() }; 15]
+    pub(crate) fn new_set_style_buffer() -> [MaybeUninit<u8>; 29] {
+        [const { MaybeUninit::uninit() }; 29]
     }
 
     /// Writes the ANSI control sequence that sets this style to the specified buffer and returns a
     /// string containing the control sequence.
-    pub(crate) fn set_style(self, buffer: &mut [MaybeUninit<u8>; 15]) -> &str {
+    pub(crate) fn set_style(self, buffer: &mut [MaybeUninit<u8>; 29]) -> &str {
         // Stores the Control Sequence Introducer (CSI) in the buffer if it is empty, otherwise
         // appends a semicolon to the buffer. Updates the number of bytes stored in the buffer.
         #[inline]
-        fn push_prefix(buffer: &mut [MaybeUninit<u8>; 15], len: &mut usize) {
+        fn push_prefix(buffer: &mut [MaybeUninit<u8>; 29], len: &mut usize) {
             if *len == 0 {
                 push_str(buffer, len, "\x1b[");
             } else {
@@ -157,7 +655,7 @@ impl Style {
         // Appends an ASCII character to the buffer and updates the number of bytes stored in the
         // buffer.
         #[inline]
-        fn push_ascii(buffer: &mut [MaybeUninit<u8>; 15], len: &mut usize, ch: u8) {
+        fn push_ascii(buffer: &mut [MaybeUninit<u8>; 29], len: &mut usize, ch: u8) {
             assert!(ch.is_ascii());
             buffer[*len].write(ch);
             *len += 1;
@@ -166,7 +664,7 @@ impl Style {
         // Appends a string slice to the buffer and updates the number of bytes stored in the
         // buffer.
         #[inline]
-        fn push_str(buffer: &mut [MaybeUninit<u8>; 15], len: &mut usize, string: &str) {
+        fn push_str(buffer: &mut [MaybeUninit<u8>; 29], len: &mut usize, string: &str) {
             let string_ptr = string.as_bytes().as_ptr();
             let string_len = string.len();
             // SAFETY: `string` is reconstructed from its original raw pointer and length, so merely
@@ -196,6 +694,16 @@ impl Style {
             push_ascii(buffer, &mut len, b'1');
         }
 
+        if self.dim {
+            push_prefix(buffer, &mut len);
+            push_ascii(buffer, &mut len, b'2');
+        }
+
+        if self.italic {
+            push_prefix(buffer, &mut len);
+            push_ascii(buffer, &mut len, b'3');
+        }
+
         if self.underlined {
             push_prefix(buffer, &mut len);
             push_ascii(buffer, &mut len, b'4');
@@ -206,6 +714,34 @@ impl Style {
             push_ascii(buffer, &mut len, b'5');
         }
 
+        if self.reverse {
+            push_prefix(buffer, &mut len);
+            push_ascii(buffer, &mut len, b'7');
+        }
+
+        if self.strikethrough {
+            push_prefix(buffer, &mut len);
+            push_ascii(buffer, &mut len, b'9');
+        }
+
+        if let Some(font @ 0..=9) = self.font {
+            push_prefix(buffer, &mut len);
+            push_ascii(buffer, &mut len, b'1');
+            push_ascii(buffer, &mut len, b'0' + font);
+        }
+
+        if let Some(position) = self.script_position {
+            push_prefix(buffer, &mut len);
+            push_str(
+                buffer,
+                &mut len,
+                match position {
+                    ScriptPosition::Superscript => "73",
+                    ScriptPosition::Subscript => "74",
+                },
+            );
+        }
+
         if len != 0 {
             push_ascii(buffer, &mut len, b'm');
         }
@@ -223,6 +759,219 @@ impl Style {
 mod tests {
     use super::*;
 
+    #[test]
+    fn builder() {
+        const STYLE: Style = Style::new()
+            .fg(Color::Red)
+            .bg(Color::Black)
+            .bold()
+            .underlined()
+            .italic()
+            .with_font(2);
+        assert_eq!(
+            STYLE,
+            Style {
+                foreground_color: Color::Red,
+                background_color: Color::Black,
+                bold: true,
+                underlined: true,
+                italic: true,
+                font: Some(2),
+                ..Default::default()
+            }
+        );
+        assert_eq!(Style::new(), Style::default());
+        assert_eq!(
+            Style::new()
+                .blinking()
+                .strikethrough()
+                .dim()
+                .reverse()
+                .with_script_position(ScriptPosition::Subscript),
+            Style {
+                blinking: true,
+                strikethrough: true,
+                dim: true,
+                reverse: true,
+                script_position: Some(ScriptPosition::Subscript),
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn with_bright_as_bold_basic_colors() {
+        let style = Style {
+            foreground_color: Color::Red,
+            background_color: Color::LightGray,
+            underlined: true,
+            ..Default::default()
+        };
+        assert_eq!(style.with_bright_as_bold(), style);
+    }
+
+    #[test]
+    fn with_bright_as_bold_foreground_color() {
+        let style = Style {
+            foreground_color: Color::LightRed,
+            ..Default::default()
+        };
+        let expected = Style {
+            foreground_color: Color::Red,
+            bold: true,
+            ..Default::default()
+        };
+        assert_eq!(style.with_bright_as_bold(), expected);
+    }
+
+    #[test]
+    fn with_bright_as_bold_background_color() {
+        let style = Style {
+            foreground_color: Color::DarkGray,
+            background_color: Color::White,
+            ..Default::default()
+        };
+        let expected = Style {
+            foreground_color: Color::Black,
+            background_color: Color::LightGray,
+            bold: true,
+            ..Default::default()
+        };
+        assert_eq!(style.with_bright_as_bold(), expected);
+        let mut buffer = Style::new_set_style_buffer();
+        assert_eq!(
+            style.with_bright_as_bold().set_style(&mut buffer),
+            "\x1b[30;47;1m"
+        );
+    }
+
+    #[test]
+    fn has_readable_colors() {
+        let readable = |foreground_color, background_color| {
+            Style {
+                foreground_color,
+                background_color,
+                ..Default::default()
+            }
+            .has_readable_colors()
+        };
+        assert!(readable(Color::Default, Color::Default));
+        assert!(readable(Color::Default, Color::Black));
+        assert!(readable(Color::White, Color::Default));
+        assert!(readable(Color::White, Color::Blue));
+        assert!(readable(Color::LightYellow, Color::Black));
+        assert!(!readable(Color::Green, Color::Green));
+        assert!(!readable(Color::LightRed, Color::Red));
+        assert!(!readable(Color::Black, Color::DarkGray));
+        assert!(!readable(Color::LightGray, Color::White));
+    }
+
+    #[test]
+    fn with_readable_colors_dark_background() {
+        let style = Style {
+            foreground_color: Color::Blue,
+            background_color: Color::LightBlue,
+            bold: true,
+            ..Default::default()
+        };
+        let expected = Style {
+            foreground_color: Color::White,
+            ..style
+        };
+        assert_eq!(style.with_readable_colors(), expected);
+    }
+
+    #[test]
+    fn with_readable_colors_light_background() {
+        let style = Style {
+            foreground_color: Color::LightGray,
+            background_color: Color::White,
+            ..Default::default()
+        };
+        let expected = Style {
+            foreground_color: Color::Black,
+            ..style
+        };
+        assert_eq!(style.with_readable_colors(), expected);
+    }
+
+    #[test]
+    fn with_readable_colors_unchanged() {
+        let style = Style {
+            foreground_color: Color::Red,
+            background_color: Color::LightCyan,
+            ..Default::default()
+        };
+        assert_eq!(style.with_readable_colors(), style);
+    }
+
+    #[test]
+    fn nearest_color() {
+        assert_eq!(Color::nearest([0, 0, 0]), Color::Black);
+        assert_eq!(Color::nearest([250, 10, 10]), Color::LightRed);
+        assert_eq!(Color::nearest([190, 20, 0]), Color::Red);
+        assert_eq!(Color::nearest([120, 130, 125]), Color::DarkGray);
+        assert_eq!(Color::nearest([255, 255, 240]), Color::White);
+    }
+
+    #[test]
+    fn from_ansi_256() {
+        assert_eq!(Color::from_ansi_256(1), Color::Red);
+        assert_eq!(Color::from_ansi_256(15), Color::White);
+        assert_eq!(Color::from_ansi_256(196), Color::LightRed);
+        assert_eq!(Color::from_ansi_256(28), Color::Green);
+        assert_eq!(Color::from_ansi_256(232), Color::Black);
+        assert_eq!(Color::from_ansi_256(250), Color::LightGray);
+    }
+
+    #[test]
+    fn with_color_depth() {
+        let style = Style::new().fg(Color::Red).bg(Color::White).bold();
+        assert_eq!(style.with_color_depth(ColorDepth::Ansi16), style);
+        assert_eq!(style.with_color_depth(ColorDepth::TrueColor), style);
+        assert_eq!(
+            style.with_color_depth(ColorDepth::NoColor),
+            Style::new().bold()
+        );
+    }
+
+    #[test]
+    fn with_sgr_parameters() {
+        let style = Style::default().with_sgr_parameters("38;5;196;1;48;2;1;2;3;94");
+        assert_eq!(
+            style,
+            Style {
+                foreground_color: Color::LightBlue,
+                background_color: Color::Black,
+                bold: true,
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            Style::default().with_sgr_parameters("38;5;196;48;5;9"),
+            Style::new().fg(Color::LightRed).bg(Color::LightRed)
+        );
+        assert_eq!(
+            style.with_sgr_parameters("?25;x;38;5;300;4"),
+            Style {
+                underlined: true,
+                ..style
+            }
+        );
+        assert_eq!(style.with_sgr_parameters("1;;4"), Style::new().underlined());
+        assert_eq!(style.with_sgr_parameters("12").font, Some(2));
+        assert_eq!(style.with_font(2).with_sgr_parameters("10").font, None);
+        assert_eq!(
+            Style::default().with_sgr_parameters("1;2;3;7;9;22;29"),
+            Style {
+                italic: true,
+                reverse: true,
+                ..Default::default()
+            }
+        );
+        assert_eq!(style.with_sgr_parameters(""), Style::default());
+    }
+
     #[test]
     fn set_style_default() {
         let style = Style::default();
@@ -306,9 +1055,72 @@ mod tests {
             bold: true,
             underlined: true,
             blinking: true,
+            italic: true,
+            strikethrough: true,
+            dim: true,
+            reverse: true,
+            font: Some(9),
+            script_position: Some(ScriptPosition::Superscript),
         };
         let mut buffer = Style::new_set_style_buffer();
         let result = style.set_style(&mut buffer);
-        assert_eq!(result, "\x1b[36;100;1;4;5m");
+        assert_eq!(result, "\x1b[36;100;1;2;3;4;5;7;9;19;73m");
+    }
+
+    #[test]
+    fn set_style_italic_strikethrough_dim_reverse() {
+        let mut buffer = Style::new_set_style_buffer();
+        let italic = Style {
+            italic: true,
+            ..Default::default()
+        };
+        assert_eq!(italic.set_style(&mut buffer), "\x1b[3m");
+        let strikethrough = Style {
+            strikethrough: true,
+            ..Default::default()
+        };
+        assert_eq!(strikethrough.set_style(&mut buffer), "\x1b[9m");
+        let dim_reverse = Style {
+            foreground_color: Color::Red,
+            dim: true,
+            reverse: true,
+            ..Default::default()
+        };
+        assert_eq!(dim_reverse.set_style(&mut buffer), "\x1b[31;2;7m");
+        let bold_italic_strikethrough = Style {
+            bold: true,
+            italic: true,
+            strikethrough: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            bold_italic_strikethrough.set_style(&mut buffer),
+            "\x1b[1;3;9m"
+        );
+    }
+
+    #[test]
+    fn set_style_subscript() {
+        let style = Style {
+            script_position: Some(ScriptPosition::Subscript),
+            ..Default::default()
+        };
+        let mut buffer = Style::new_set_style_buffer();
+        let result = style.set_style(&mut buffer);
+        assert_eq!(result, "\x1b[74m");
+    }
+
+    #[test]
+    fn set_style_font() {
+        let mut style = Style {
+            font: Some(3),
+            ..Default::default()
+        };
+        let mut buffer = Style::new_set_style_buffer();
+        assert_eq!(style.set_style(&mut buffer), "\x1b[13m");
+        style.font = Some(0);
+        assert_eq!(style.set_style(&mut buffer), "\x1b[10m");
+        style.font = Some(10);
+        assert!(style.set_style(&mut buffer).is_empty());
     }
 }
@@ -0,0 +1,169 @@
+//! Conversions between [`Style`] and `termcolor` color specifications.
+
+use termcolor::ColorSpec;
+
+use crate::{Color, Style};
+
+/// The `termcolor` colors that correspond to the colors of the basic 8-color palette, in the order
+/// of their ANSI color numbers.
+const BASE_COLORS: [termcolor::Color; 8] = [
+    termcolor::Color::Black,
+    termcolor::Color::Red,
+    termcolor::Color::Green,
+    termcolor::Color::Yellow,
+    termcolor::Color::Blue,
+    termcolor::Color::Magenta,
+    termcolor::Color::Cyan,
+    termcolor::Color::White,
+];
+
+/// Converts a style to a `termcolor` color specification, e.g., to write styled output through
+/// `termcolor::WriteColor`.
+///
+/// The intense flag of the specification applies to both the foreground and the background color.
+/// A bright color whose intensity differs from the flag is converted to the corresponding color of
+/// the 256-color palette. Blinking text, reversed colors, fonts, and script positions cannot be
+/// represented and are omitted.
+impl From<Style> for ColorSpec {
+    fn from(style: Style) -> Self {
+        let intense = if style.foreground_color == Color::Default {
+            is_bright(style.background_color)
+        } else {
+            is_bright(style.foreground_color)
+        };
+        let mut spec = Self::new();
+        spec.set_fg(to_termcolor(style.foreground_color, intense))
+            .set_bg(to_termcolor(style.background_color, intense))
+            .set_intense(intense)
+            .set_bold(style.bold)
+            .set_dimmed(style.dim)
+            .set_italic(style.italic)
+            .set_underline(style.underlined)
+            .set_strikethrough(style.strikethrough);
+        spec
+    }
+}
+
+/// Converts a `termcolor` color specification to a style, e.g., to render output of libraries that
+/// use `termcolor`.
+///
+/// Colors of the 256-color palette and RGB colors are replaced by the [nearest](Color::nearest)
+/// color of the 16-color palette.
+impl From<&ColorSpec> for Style {
+    fn from(spec: &ColorSpec) -> Self {
+        Self {
+            foreground_color: spec.fg().map_or(Color::Default, |&color| {
+                from_termcolor(color, spec.intense())
+            }),
+            background_color: spec.bg().map_or(Color::Default, |&color| {
+                from_termcolor(color, spec.intense())
+            }),
+            bold: spec.bold(),
+            dim: spec.dimmed(),
+            italic: spec.italic(),
+            underlined: spec.underline(),
+            strikethrough: spec.strikethrough(),
+            ..Self::default()
+        }
+    }
+}
+
+/// Returns the index of the color in the 16-color palette, or `None` if it is the default color.
+#[must_use]
+fn palette_index(color: Color) -> Option<usize> {
+    Color::PALETTE
+        .iter()
+        .position(|&palette_color| palette_color == color)
+}
+
+/// Returns whether the color is a bright color of the 16-color palette.
+#[must_use]
+fn is_bright(color: Color) -> bool {
+    palette_index(color).is_some_and(|index| index >= 8)
+}
+
+/// Returns the `termcolor` color that displays the color in a specification with the specified
+/// intense flag, or `None` if the color is the default color.
+#[must_use]
+fn to_termcolor(color: Color, intense: bool) -> Option<termcolor::Color> {
+    let index = palette_index(color)?;
+    if (index >= 8) == intense {
+        Some(BASE_COLORS[index % 8])
+    } else {
+        u8::try_from(index).ok().map(termcolor::Color::Ansi256)
+    }
+}
+
+/// Returns the color that is displayed for the `termcolor` color in a specification with the
+/// specified intense flag.
+#[must_use]
+fn from_termcolor(color: termcolor::Color, intense: bool) -> Color {
+    match color {
+        termcolor::Color::Ansi256(index) => Color::from_ansi_256(index),
+        termcolor::Color::Rgb(red, green, blue) => Color::nearest([red, green, blue]),
+        _ => BASE_COLORS
+            .iter()
+            .position(|&base_color| base_color == color)
+            .map_or(Color::Default, |index| {
+                Color::PALETTE[index + if intense { 8 } else { 0 }]
+            }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn style_to_color_spec() {
+        let spec = ColorSpec::from(
+            Style::new()
+                .fg(Color::LightRed)
+                .bg(Color::Blue)
+                .bold()
+                .italic(),
+        );
+        assert_eq!(spec.fg(), Some(&termcolor::Color::Red));
+        assert_eq!(spec.bg(), Some(&termcolor::Color::Ansi256(4)));
+        assert!(spec.intense());
+        assert!(spec.bold());
+        assert!(spec.italic());
+        assert!(!spec.underline());
+
+        let default = ColorSpec::from(Style::new().bg(Color::White));
+        assert_eq!(default.fg(), None);
+        assert_eq!(default.bg(), Some(&termcolor::Color::White));
+        assert!(default.intense());
+    }
+
+    #[test]
+    fn color_spec_to_style() {
+        let mut spec = ColorSpec::new();
+        spec.set_fg(Some(termcolor::Color::Cyan))
+            .set_bg(Some(termcolor::Color::Rgb(250, 250, 250)))
+            .set_underline(true)
+            .set_dimmed(true);
+        assert_eq!(
+            Style::from(&spec),
+            Style::new()
+                .fg(Color::Cyan)
+                .bg(Color::White)
+                .underlined()
+                .dim()
+        );
+        spec.set_intense(true)
+            .set_bg(Some(termcolor::Color::Ansi256(9)));
+        assert_eq!(
+            Style::from(&spec),
+            Style::new()
+                .fg(Color::LightCyan)
+                .bg(Color::LightRed)
+                .underlined()
+                .dim()
+        );
+        assert_eq!(
+            ColorSpec::from(Style::from(&spec)).fg(),
+            Some(&termcolor::Color::Cyan)
+        );
+    }
+}
@@ -0,0 +1,967 @@
+//! Terminal control.
+//!
+//! The functions in this module write control sequences that change the state of the terminal
+//! rather than the style of text. They only write to streams that are interactive terminals, so
+//! that output redirected to a file or pipe is not corrupted.
+//!
+//! All control sequences written by this crate use the 7-bit representation (e.g., `ESC [` rather
+//! than the single character CSI). The functions that write Operating System Commands take an
+//! [`OscTerminator`], so that BEL can be selected for terminals that do not accept the String
+//! Terminator.
+
+use std::{
+    env,
+    ffi::OsString,
+    io::{self, IsTerminal, Write},
+};
+
+use crate::{
+    Color,
+    guard::{Finish, FinishOnDrop},
+};
+
+/// ANSI control sequence that switches to the alternate screen buffer.
+const ENTER_ALTERNATE_SCREEN: &str = "\x1b[?1049h";
+
+/// ANSI control sequence that switches back from the alternate screen buffer.
+const LEAVE_ALTERNATE_SCREEN: &str = "\x1b[?1049l";
+
+/// ANSI control sequence that resets the scroll region to the whole screen.
+const RESET_SCROLL_REGION: &str = "\x1b[r";
+
+/// ANSI control sequence that saves the cursor position.
+const SAVE_CURSOR: &str = "\x1b[s";
+
+/// ANSI control sequence that restores the saved cursor position.
+const RESTORE_CURSOR: &str = "\x1b[u";
+
+/// ANSI control sequence that starts an Operating System Command (OSC).
+const OSC: &str = "\x1b]";
+
+/// Terminator of Operating System Commands (OSC).
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum OscTerminator {
+    /// The 7-bit String Terminator `ESC \`, as specified by ECMA-48.
+    #[default]
+    St,
+    /// The BEL character, which is required by some legacy terminals and serial consoles.
+    Bel,
+}
+
+impl OscTerminator {
+    /// Returns the ANSI control sequence of the terminator.
+    #[inline]
+    #[must_use]
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::St => "\x1b\\",
+            Self::Bel => "\x07",
+        }
+    }
+}
+
+/// Number of colors supported by a terminal.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[non_exhaustive]
+pub enum ColorDepth {
+    /// No colors.
+    NoColor,
+    /// The 16 colors of the basic palette, as represented by [`Color`].
+    Ansi16,
+    /// The 256 colors of the xterm palette.
+    Ansi256,
+    /// 24-bit RGB colors.
+    TrueColor,
+}
+
+impl ColorDepth {
+    /// Returns the color depth supported by the terminal, as inferred from environment variables
+    /// and the platform.
+    ///
+    /// The following environment variables are consulted, in order of precedence:
+    ///
+    /// 1. `FORCE_COLOR`: `0` or `false` disables colors, `2` selects 256 colors, `3` selects 24-bit
+    ///    colors, and any other value enables at least 16 colors.
+    /// 2. `CLICOLOR_FORCE`: Any value except `0` enables at least 16 colors.
+    /// 3. `NO_COLOR`: Any non-empty value disables colors.
+    /// 4. `CLICOLOR`: `0` disables colors.
+    /// 5. `COLORTERM`, `TERM_PROGRAM`, and `TERM`, from which the colors supported by the terminal
+    ///    are inferred. Colors are disabled if `TERM` is `dumb`, or if it is not set on Unix-like
+    ///    platforms.
+    ///
+    /// This does not check whether a stream is an interactive terminal. `FORCE_COLOR` and
+    /// `CLICOLOR_FORCE` conventionally request colors even for output that is not written to an
+    /// interactive terminal.
+    #[must_use]
+    pub fn detect() -> Self {
+        Self::detect_from(|name| env::var_os(name))
+    }
+
+    /// Returns the color depth supported by the terminal, as inferred from environment variables
+    /// that are looked up with the specified function.
+    fn detect_from<F>(var: F) -> Self
+    where
+        F: Fn(&str) -> Option<OsString>,
+    {
+        if let Some(force_color) = var("FORCE_COLOR") {
+            return match force_color.to_str() {
+                Some("0" | "false") => Self::NoColor,
+                Some("2") => Self::Ansi256.max(Self::infer_from(&var)),
+                Some("3") => Self::TrueColor,
+                _ => Self::Ansi16.max(Self::infer_from(&var)),
+            };
+        }
+        if var("CLICOLOR_FORCE").is_some_and(|value| value != "0") {
+            return Self::Ansi16.max(Self::infer_from(&var));
+        }
+        if var("NO_COLOR").is_some_and(|value| !value.is_empty())
+            || var("CLICOLOR").is_some_and(|value| value == "0")
+        {
+            return Self::NoColor;
+        }
+        Self::infer_from(&var)
+    }
+
+    /// Returns the color depth supported by the terminal, as inferred from the terminal-specific
+    /// environment variables that are looked up with the specified function.
+    fn infer_from<F>(var: &F) -> Self
+    where
+        F: Fn(&str) -> Option<OsString>,
+    {
+        let colorterm = var("COLORTERM");
+        if matches!(
+            colorterm.as_ref().and_then(|value| value.to_str()),
+            Some("truecolor" | "24bit")
+        ) {
+            return Self::TrueColor;
+        }
+
+        let term_program = var("TERM_PROGRAM");
+        match term_program.as_ref().and_then(|value| value.to_str()) {
+            Some("ghostty" | "iTerm.app" | "WezTerm") => return Self::TrueColor,
+            Some("Apple_Terminal") => return Self::Ansi256,
+            _ => {}
+        }
+
+        let term = var("TERM");
+        match term.as_ref().and_then(|value| value.to_str()) {
+            Some(term) if term.ends_with("-direct") || term == "xterm-kitty" => Self::TrueColor,
+            Some(term) if term.ends_with("256color") => Self::Ansi256,
+            Some("dumb") => Self::NoColor,
+            // Windows Terminal supports 24-bit colors, but does not set `TERM`.
+            None if cfg!(windows) && var("WT_SESSION").is_some() => Self::TrueColor,
+            // Terminals on Unix-like platforms set `TERM`, so it is likely not a terminal.
+            None if cfg!(unix) => Self::NoColor,
+            _ => Self::Ansi16,
+        }
+    }
+}
+
+/// Protocol for desktop notifications sent through the terminal.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum NotificationProtocol {
+    /// `OSC 9`, which supports a message body only (iTerm2, kitty, and others).
+    Osc9,
+    /// `OSC 777`, which supports a title and a message body (foot, urxvt, and others).
+    Osc777,
+}
+
+impl NotificationProtocol {
+    /// Returns the notification protocol supported by the terminal, as inferred from environment
+    /// variables, or `None` if the terminal is not known to support desktop notifications.
+    #[must_use]
+    pub fn detect() -> Option<Self> {
+        Self::detect_from(|name| env::var_os(name))
+    }
+
+    /// Returns the notification protocol supported by the terminal, as inferred from environment
+    /// variables that are looked up with the specified function.
+    fn detect_from<F>(var: F) -> Option<Self>
+    where
+        F: Fn(&str) -> Option<OsString>,
+    {
+        let term_program = var("TERM_PROGRAM");
+        match term_program.as_ref().and_then(|value| value.to_str()) {
+            Some("ghostty" | "WezTerm") => return Some(Self::Osc777),
+            Some("iTerm.app") => return Some(Self::Osc9),
+            _ => {}
+        }
+
+        let term = var("TERM");
+        match term.as_ref().and_then(|value| value.to_str()) {
+            Some(term) if term.starts_with("foot") || term.starts_with("rxvt-unicode") => {
+                Some(Self::Osc777)
+            }
+            Some("xterm-kitty") => Some(Self::Osc9),
+            _ => None,
+        }
+    }
+}
+
+/// Guard that keeps the terminal in the alternate screen buffer while it exists.
+///
+/// The alternate screen buffer has no scrollback, and the contents of the normal screen buffer are
+/// restored when the terminal switches back. This allows full-screen views without scrolling the
+/// user's history away. The guard switches back when it is dropped, including during unwinding
+/// after a panic. Errors that occur while switching back on drop are ignored; use
+/// [`leave`](Self::leave) to handle them.
+///
+/// The guard implements [`Write`], so output can be written to the stream through it.
+#[derive(Debug)]
+pub struct AlternateScreen<W>
+where
+    W: Write,
+{
+    /// The stream to which control sequences and output are written, and whether the alternate
+    /// screen buffer must be left.
+    inner: FinishOnDrop<W, Restore>,
+}
+
+impl<W> AlternateScreen<W>
+where
+    W: Write,
+{
+    /// Switches to the alternate screen buffer and returns a guard that switches back when it is
+    /// dropped.
+    ///
+    /// Nothing is written, neither now nor when the guard is dropped, if `stream` is not an
+    /// interactive terminal.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing to or flushing the stream fails.
+    pub fn enter(stream: W) -> io::Result<Self>
+    where
+        W: IsTerminal,
+    {
+        if stream.is_terminal() {
+            Self::enter_unchecked(stream)
+        } else {
+            Ok(Self::new(stream, false))
+        }
+    }
+
+    /// Switches to the alternate screen buffer without checking whether `stream` is an interactive
+    /// terminal, and returns a guard that switches back when it is dropped.
+    fn enter_unchecked(mut stream: W) -> io::Result<Self> {
+        stream.write_all(ENTER_ALTERNATE_SCREEN.as_bytes())?;
+        stream.flush()?;
+        Ok(Self::new(stream, true))
+    }
+
+    /// Returns a guard that switches back from the alternate screen buffer when it is dropped if
+    /// `active` is `true`.
+    #[inline]
+    #[must_use]
+    const fn new(stream: W, active: bool) -> Self {
+        Self {
+            inner: FinishOnDrop::new(
+                stream,
+                Restore {
+                    sequence: LEAVE_ALTERNATE_SCREEN,
+                    active,
+                },
+            ),
+        }
+    }
+
+    /// Returns whether the alternate screen buffer has been entered.
+    ///
+    /// This is `false` if the stream is not an interactive terminal.
+    #[inline]
+    #[must_use]
+    pub const fn is_active(&self) -> bool {
+        self.inner.state.active
+    }
+
+    /// Returns a reference to the stream.
+    #[inline]
+    #[must_use]
+    pub const fn get_ref(&self) -> &W {
+        &self.inner.stream
+    }
+
+    /// Returns a mutable reference to the stream.
+    #[inline]
+    #[must_use]
+    pub const fn get_mut(&mut self) -> &mut W {
+        &mut self.inner.stream
+    }
+
+    /// Switches back from the alternate screen buffer and returns the stream.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing to or flushing the stream fails.
+    #[inline]
+    pub fn leave(self) -> io::Result<W> {
+        self.inner.into_stream()
+    }
+}
+
+impl<W> Write for AlternateScreen<W>
+where
+    W: Write,
+{
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.inner.stream.write(buf)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.stream.flush()
+    }
+}
+
+/// Guard that restricts scrolling to a region of the terminal screen while it exists.
+///
+/// Output that reaches the bottom of the scroll region scrolls only the lines within the region, so
+/// lines outside of it, e.g., a pinned header or footer status bar, stay in place. The guard resets
+/// the scroll region to the whole screen when it is dropped, including during unwinding after a
+/// panic. Errors that occur while resetting on drop are ignored; use [`reset`](Self::reset) to
+/// handle them. Terminals move the cursor to the top left corner of the screen both when the scroll
+/// region is set and when it is reset.
+///
+/// The guard implements [`Write`], so output can be written to the stream through it.
+#[derive(Debug)]
+pub struct ScrollRegion<W>
+where
+    W: Write,
+{
+    /// The stream to which control sequences and output are written, and whether the scroll region
+    /// must be reset.
+    inner: FinishOnDrop<W, Restore>,
+}
+
+impl<W> ScrollRegion<W>
+where
+    W: Write,
+{
+    /// Restricts scrolling to the rows from `top` to `bottom` (inclusive), counted from zero at the
+    /// top of the screen, and returns a guard that resets the scroll region when it is dropped.
+    ///
+    /// Nothing is written, neither now nor when the guard is dropped, if `stream` is not an
+    /// interactive terminal. Terminals ignore the request if `bottom` is not greater than `top` or
+    /// not on the screen.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing to or flushing the stream fails.
+    pub fn set(stream: W, top: usize, bottom: usize) -> io::Result<Self>
+    where
+        W: IsTerminal,
+    {
+        if stream.is_terminal() {
+            Self::set_unchecked(stream, top, bottom)
+        } else {
+            Ok(Self::new(stream, false))
+        }
+    }
+
+    /// Restricts scrolling to the specified rows without checking whether `stream` is an
+    /// interactive terminal, and returns a guard that resets the scroll region when it is dropped.
+    fn set_unchecked(mut stream: W, top: usize, bottom: usize) -> io::Result<Self> {
+        write!(
+            stream,
+            "\x1b[{};{}r",
+            top.saturating_add(1),
+            bottom.saturating_add(1)
+        )?;
+        stream.flush()?;
+        Ok(Self::new(stream, true))
+    }
+
+    /// Returns a guard that resets the scroll region when it is dropped if `active` is `true`.
+    #[inline]
+    #[must_use]
+    const fn new(stream: W, active: bool) -> Self {
+        Self {
+            inner: FinishOnDrop::new(
+                stream,
+                Restore {
+                    sequence: RESET_SCROLL_REGION,
+                    active,
+                },
+            ),
+        }
+    }
+
+    /// Returns whether the scroll region has been set.
+    ///
+    /// This is `false` if the stream is not an interactive terminal.
+    #[inline]
+    #[must_use]
+    pub const fn is_active(&self) -> bool {
+        self.inner.state.active
+    }
+
+    /// Returns a reference to the stream.
+    #[inline]
+    #[must_use]
+    pub const fn get_ref(&self) -> &W {
+        &self.inner.stream
+    }
+
+    /// Returns a mutable reference to the stream.
+    #[inline]
+    #[must_use]
+    pub const fn get_mut(&mut self) -> &mut W {
+        &mut self.inner.stream
+    }
+
+    /// Resets the scroll region to the whole screen and returns the stream.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing to or flushing the stream fails.
+    #[inline]
+    pub fn reset(self) -> io::Result<W> {
+        self.inner.into_stream()
+    }
+}
+
+impl<W> Write for ScrollRegion<W>
+where
+    W: Write,
+{
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.inner.stream.write(buf)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.stream.flush()
+    }
+}
+
+/// State of a guard that restores the state of the terminal by writing a control sequence.
+#[derive(Debug)]
+struct Restore {
+    /// The ANSI control sequence that restores the state of the terminal.
+    sequence: &'static str,
+    /// Whether the state of the terminal has been changed and must be restored.
+    active: bool,
+}
+
+impl<W> Finish<W> for Restore
+where
+    W: Write,
+{
+    /// Restores the state of the terminal if it has been changed.
+    fn finish(&mut self, stream: &mut W) -> io::Result<()> {
+        if self.active {
+            self.active = false;
+            stream.write_all(self.sequence.as_bytes())?;
+            stream.flush()?;
+        }
+        Ok(())
+    }
+}
+
+/// Rings the terminal bell.
+///
+/// Nothing is written if `stream` is not an interactive terminal.
+///
+/// # Errors
+///
+/// Returns an error if writing to or flushing the stream fails.
+pub fn bell<W>(stream: &mut W) -> io::Result<()>
+where
+    W: Write + IsTerminal + ?Sized,
+{
+    if stream.is_terminal() {
+        stream.write_all(b"\x07")?;
+        stream.flush()?;
+    }
+    Ok(())
+}
+
+/// Sends a desktop notification through the terminal, and returns whether it was sent.
+///
+/// The notification is only sent if `stream` is an interactive terminal and a
+/// [`NotificationProtocol`] supported by the terminal is [detected](NotificationProtocol::detect).
+/// Use [`notify_with`] to specify the protocol explicitly. Control characters are removed from the
+/// title and body, and the notification is terminated by `terminator`.
+///
+/// # Errors
+///
+/// Returns an error if writing to or flushing the stream fails.
+pub fn notify<W>(
+    stream: &mut W,
+    title: &str,
+    body: &str,
+    terminator: OscTerminator,
+) -> io::Result<bool>
+where
+    W: Write + IsTerminal + ?Sized,
+{
+    NotificationProtocol::detect().map_or(Ok(false), |protocol| {
+        notify_with(stream, protocol, title, body, terminator)
+    })
+}
+
+/// Sends a desktop notification through the terminal using the specified protocol, and returns
+/// whether it was sent.
+///
+/// The notification is only sent if `stream` is an interactive terminal. Control characters are
+/// removed from the title and body. The title is omitted with [`NotificationProtocol::Osc9`], which
+/// does not support titles. The notification is terminated by `terminator`.
+///
+/// # Errors
+///
+/// Returns an error if writing to or flushing the stream fails.
+pub fn notify_with<W>(
+    stream: &mut W,
+    protocol: NotificationProtocol,
+    title: &str,
+    body: &str,
+    terminator: OscTerminator,
+) -> io::Result<bool>
+where
+    W: Write + IsTerminal + ?Sized,
+{
+    if stream.is_terminal() {
+        write_notification(stream, protocol, title, body, terminator)?;
+        stream.flush()?;
+        Ok(true)
+    } else {
+        Ok(false)
+    }
+}
+
+/// Sets the title of the terminal window or tab.
+///
+/// Nothing is written if `stream` is not an interactive terminal. Control characters are removed
+/// from the title, and the control sequence is terminated by `terminator`.
+///
+/// # Errors
+///
+/// Returns an error if writing to or flushing the stream fails.
+pub fn set_title<W>(stream: &mut W, title: &str, terminator: OscTerminator) -> io::Result<()>
+where
+    W: Write + IsTerminal + ?Sized,
+{
+    if stream.is_terminal() {
+        write_title(stream, title, terminator)?;
+        stream.flush()?;
+    }
+    Ok(())
+}
+
+/// Saves the cursor position, so that it can be restored with [`restore_cursor`].
+///
+/// Nothing is written if `stream` is not an interactive terminal.
+///
+/// # Errors
+///
+/// Returns an error if writing to or flushing the stream fails.
+pub fn save_cursor<W>(stream: &mut W) -> io::Result<()>
+where
+    W: Write + IsTerminal + ?Sized,
+{
+    if stream.is_terminal() {
+        stream.write_all(SAVE_CURSOR.as_bytes())?;
+        stream.flush()?;
+    }
+    Ok(())
+}
+
+/// Restores the cursor position saved with [`save_cursor`].
+///
+/// Nothing is written if `stream` is not an interactive terminal.
+///
+/// # Errors
+///
+/// Returns an error if writing to or flushing the stream fails.
+pub fn restore_cursor<W>(stream: &mut W) -> io::Result<()>
+where
+    W: Write + IsTerminal + ?Sized,
+{
+    if stream.is_terminal() {
+        stream.write_all(RESTORE_CURSOR.as_bytes())?;
+        stream.flush()?;
+    }
+    Ok(())
+}
+
+/// Moves the cursor to the specified row and column, counted from zero at the top left corner of
+/// the screen.
+///
+/// Nothing is written if `stream` is not an interactive terminal. Terminals clamp positions outside
+/// the screen to its edges.
+///
+/// # Errors
+///
+/// Returns an error if writing to or flushing the stream fails.
+pub fn move_cursor_to<W>(stream: &mut W, row: usize, column: usize) -> io::Result<()>
+where
+    W: Write + IsTerminal + ?Sized,
+{
+    if stream.is_terminal() {
+        write_cursor_position(stream, row, column)?;
+        stream.flush()?;
+    }
+    Ok(())
+}
+
+/// Asks the terminal to report the 16 colors of its palette, and returns whether the query was
+/// sent.
+///
+/// The query is only sent if `stream` is an interactive terminal, and each of its control sequences
+/// is terminated by `terminator`. Terminals that support the query (xterm, kitty, foot, and many
+/// others) reply on their input with one Operating System Command (OSC 4) per color, which can be
+/// parsed with [`parse_palette_reply`]. Reading the reply is left to the caller, because it
+/// requires switching the terminal input to raw mode. Since terminals that do not support the query
+/// do not reply at all, the caller should stop waiting for the reply after a timeout and fall back
+/// to the [default colors](Color::rgb).
+///
+/// # Errors
+///
+/// Returns an error if writing to or flushing the stream fails.
+pub fn query_palette<W>(stream: &mut W, terminator: OscTerminator) -> io::Result<bool>
+where
+    W: Write + IsTerminal + ?Sized,
+{
+    if stream.is_terminal() {
+        write_palette_query(stream, terminator)?;
+        stream.flush()?;
+        Ok(true)
+    } else {
+        Ok(false)
+    }
+}
+
+/// Returns the colors and their red, green, and blue components reported by the terminal in reply
+/// to [`query_palette`].
+///
+/// The colors are returned in the order in which they appear in the reply. Colors that are missing
+/// or malformed in the reply, e.g., because it was cut off by a timeout, are omitted.
+#[must_use]
+pub fn parse_palette_reply(reply: &str) -> Vec<(Color, [u8; 3])> {
+    reply
+        .split(OSC)
+        .filter_map(|sequence| {
+            let sequence = sequence.split(['\x07', '\x1b']).next()?;
+            let (index, spec) = sequence.strip_prefix("4;")?.split_once(';')?;
+            let color = *Color::PALETTE.get(index.parse::<usize>().ok()?)?;
+            let mut components = spec.strip_prefix("rgb:")?.split('/').map(parse_component);
+            let rgb = [
+                components.next()??,
+                components.next()??,
+                components.next()??,
+            ];
+            components.next().is_none().then_some((color, rgb))
+        })
+        .collect()
+}
+
+/// Returns the value of a color component with one to four hexadecimal digits, scaled to the range
+/// from 0 to 255.
+#[must_use]
+fn parse_component(digits: &str) -> Option<u8> {
+    if !(1..=4).contains(&digits.len()) || !digits.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+        return None;
+    }
+    let value = u32::from_str_radix(digits, 16).ok()?;
+    let max = (1 << (4 * digits.len())) - 1;
+    u8::try_from((value * 255 + max / 2) / max).ok()
+}
+
+/// Writes the ANSI control sequences that ask the terminal to report the 16 colors of its palette.
+fn write_palette_query<W>(stream: &mut W, terminator: OscTerminator) -> io::Result<()>
+where
+    W: Write + ?Sized,
+{
+    let st = terminator.as_str();
+    for index in 0..Color::PALETTE.len() {
+        write!(stream, "{OSC}4;{index};?{st}")?;
+    }
+    Ok(())
+}
+
+/// Writes the ANSI control sequence that moves the cursor to the specified zero-based row and
+/// column.
+fn write_cursor_position<W>(stream: &mut W, row: usize, column: usize) -> io::Result<()>
+where
+    W: Write + ?Sized,
+{
+    write!(
+        stream,
+        "\x1b[{};{}H",
+        row.saturating_add(1),
+        column.saturating_add(1)
+    )
+}
+
+/// Writes the ANSI control sequence that sets the title of the terminal window or tab.
+fn write_title<W>(stream: &mut W, title: &str, terminator: OscTerminator) -> io::Result<()>
+where
+    W: Write + ?Sized,
+{
+    let title = without_control_chars(title);
+    let st = terminator.as_str();
+    write!(stream, "{OSC}0;{title}{st}")
+}
+
+/// Writes the ANSI control sequence that sends a desktop notification.
+fn write_notification<W>(
+    stream: &mut W,
+    protocol: NotificationProtocol,
+    title: &str,
+    body: &str,
+    terminator: OscTerminator,
+) -> io::Result<()>
+where
+    W: Write + ?Sized,
+{
+    let body = without_control_chars(body);
+    let st = terminator.as_str();
+    match protocol {
+        NotificationProtocol::Osc9 => write!(stream, "{OSC}9;{body}{st}"),
+        NotificationProtocol::Osc777 => {
+            // A semicolon would terminate the title.
+            let title = without_control_chars(title).replace(';', ",");
+            write!(stream, "{OSC}777;notify;{title};{body}{st}")
+        }
+    }
+}
+
+/// Returns the text with all control characters removed.
+#[must_use]
+fn without_control_chars(text: &str) -> String {
+    text.chars().filter(|ch| !ch.is_control()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write_title_plain() {
+        let mut result = Vec::new();
+        write_title(&mut result, "Building (3/7)", OscTerminator::St)
+            .expect("writing to Vec failed");
+        assert_eq!(result, b"\x1b]0;Building (3/7)\x1b\\");
+    }
+
+    #[test]
+    fn write_title_control_characters() {
+        let mut result = Vec::new();
+        write_title(&mut result, "foo\x1b\\\x07bar\n", OscTerminator::St)
+            .expect("writing to Vec failed");
+        assert_eq!(result, b"\x1b]0;foo\\bar\x1b\\");
+    }
+
+    #[test]
+    fn write_title_bel_terminated() {
+        let mut result = Vec::new();
+        write_title(&mut result, "foo", OscTerminator::Bel).expect("writing to Vec failed");
+        assert_eq!(result, b"\x1b]0;foo\x07");
+    }
+
+    #[test]
+    fn detect_notification_protocol() {
+        let detect = |vars: &[(&str, &str)]| {
+            NotificationProtocol::detect_from(|name| {
+                vars.iter()
+                    .find(|(key, _)| *key == name)
+                    .map(|(_, value)| OsString::from(value))
+            })
+        };
+        assert_eq!(detect(&[]), None);
+        assert_eq!(detect(&[("TERM", "xterm-256color")]), None);
+        assert_eq!(
+            detect(&[("TERM", "xterm-256color"), ("TERM_PROGRAM", "iTerm.app")]),
+            Some(NotificationProtocol::Osc9)
+        );
+        assert_eq!(
+            detect(&[("TERM_PROGRAM", "WezTerm")]),
+            Some(NotificationProtocol::Osc777)
+        );
+        assert_eq!(
+            detect(&[("TERM", "xterm-kitty")]),
+            Some(NotificationProtocol::Osc9)
+        );
+        assert_eq!(
+            detect(&[("TERM", "foot-extra")]),
+            Some(NotificationProtocol::Osc777)
+        );
+    }
+
+    #[test]
+    fn detect_color_depth() {
+        let detect = |vars: &[(&str, &str)]| {
+            ColorDepth::detect_from(|name| {
+                vars.iter()
+                    .find(|(key, _)| *key == name)
+                    .map(|(_, value)| OsString::from(value))
+            })
+        };
+        assert_eq!(detect(&[("TERM", "xterm")]), ColorDepth::Ansi16);
+        assert_eq!(detect(&[("TERM", "xterm-256color")]), ColorDepth::Ansi256);
+        assert_eq!(
+            detect(&[("TERM", "xterm-256color"), ("COLORTERM", "truecolor")]),
+            ColorDepth::TrueColor
+        );
+        assert_eq!(detect(&[("TERM", "xterm-direct")]), ColorDepth::TrueColor);
+        assert_eq!(
+            detect(&[("TERM", "xterm-256color"), ("TERM_PROGRAM", "WezTerm")]),
+            ColorDepth::TrueColor
+        );
+        assert_eq!(detect(&[("TERM", "dumb")]), ColorDepth::NoColor);
+        if cfg!(unix) {
+            assert_eq!(detect(&[]), ColorDepth::NoColor);
+        }
+        assert!(ColorDepth::NoColor < ColorDepth::Ansi16);
+    }
+
+    #[test]
+    fn detect_color_depth_overrides() {
+        let detect = |vars: &[(&str, &str)]| {
+            ColorDepth::detect_from(|name| {
+                vars.iter()
+                    .find(|(key, _)| *key == name)
+                    .map(|(_, value)| OsString::from(value))
+            })
+        };
+        let term = ("TERM", "xterm-256color");
+        assert_eq!(detect(&[term, ("NO_COLOR", "1")]), ColorDepth::NoColor);
+        assert_eq!(detect(&[term, ("NO_COLOR", "")]), ColorDepth::Ansi256);
+        assert_eq!(detect(&[term, ("CLICOLOR", "0")]), ColorDepth::NoColor);
+        assert_eq!(
+            detect(&[term, ("NO_COLOR", "1"), ("CLICOLOR_FORCE", "1")]),
+            ColorDepth::Ansi256
+        );
+        assert_eq!(
+            detect(&[("TERM", "xterm"), ("FORCE_COLOR", "3")]),
+            ColorDepth::TrueColor
+        );
+        assert_eq!(
+            detect(&[term, ("CLICOLOR_FORCE", "1"), ("FORCE_COLOR", "0")]),
+            ColorDepth::NoColor
+        );
+        assert_eq!(detect(&[("FORCE_COLOR", "")]), ColorDepth::Ansi16);
+        assert_eq!(
+            detect(&[("TERM", "dumb"), ("CLICOLOR_FORCE", "1")]),
+            ColorDepth::Ansi16
+        );
+    }
+
+    #[test]
+    fn write_notification_osc9() {
+        let mut result = Vec::new();
+        write_notification(
+            &mut result,
+            NotificationProtocol::Osc9,
+            "Build",
+            "done\x07",
+            OscTerminator::St,
+        )
+        .expect("writing to Vec failed");
+        assert_eq!(result, b"\x1b]9;done\x1b\\");
+    }
+
+    #[test]
+    fn write_notification_osc777() {
+        let mut result = Vec::new();
+        write_notification(
+            &mut result,
+            NotificationProtocol::Osc777,
+            "Build; tests",
+            "finished; 0 errors",
+            OscTerminator::St,
+        )
+        .expect("writing to Vec failed");
+        assert_eq!(
+            result,
+            b"\x1b]777;notify;Build, tests;finished; 0 errors\x1b\\"
+        );
+    }
+
+    #[test]
+    fn write_cursor_position_one_based() {
+        let mut result = Vec::new();
+        write_cursor_position(&mut result, 0, 0).expect("writing to Vec failed");
+        write_cursor_position(&mut result, 23, 79).expect("writing to Vec failed");
+        assert_eq!(result, b"\x1b[1;1H\x1b[24;80H");
+    }
+
+    #[test]
+    fn write_palette_query_all_colors() {
+        let mut result = Vec::new();
+        write_palette_query(&mut result, OscTerminator::Bel).expect("writing to Vec failed");
+        let result = String::from_utf8(result).expect("query is valid UTF-8");
+        assert!(result.starts_with("\x1b]4;0;?\x07\x1b]4;1;?\x07"));
+        assert!(result.ends_with("\x1b]4;15;?\x07"));
+    }
+
+    #[test]
+    fn parse_palette_reply_colors() {
+        assert_eq!(
+            parse_palette_reply(
+                "\x1b]4;1;rgb:cdcd/0000/0000\x1b\\\x1b]4;12;rgb:5c/5c/ff\x07\
+                 \x1b]4;16;rgb:0/0/0\x07\x1b]4;2;rgb:0/cd\x07\x1b]4;3;rgb:f/8/0"
+            ),
+            [
+                (Color::Red, [0xcd, 0x00, 0x00]),
+                (Color::LightBlue, [0x5c, 0x5c, 0xff]),
+                (Color::Yellow, [0xff, 0x88, 0x00]),
+            ]
+        );
+        assert!(parse_palette_reply("").is_empty());
+    }
+
+    #[test]
+    fn scroll_region_drop() {
+        let mut result = Vec::new();
+        {
+            let mut region =
+                ScrollRegion::set_unchecked(&mut result, 0, 22).expect("writing to Vec failed");
+            assert!(region.is_active());
+            region.write_all(b"foo").expect("writing to Vec failed");
+        }
+        assert_eq!(result, b"\x1b[1;23rfoo\x1b[r");
+    }
+
+    #[test]
+    fn scroll_region_reset() {
+        let region = ScrollRegion::set_unchecked(Vec::new(), 2, 9).expect("writing to Vec failed");
+        let result = region.reset().expect("writing to Vec failed");
+        assert_eq!(result, b"\x1b[3;10r\x1b[r");
+    }
+
+    #[test]
+    fn alternate_screen_drop() {
+        let mut result = Vec::new();
+        {
+            let mut screen =
+                AlternateScreen::enter_unchecked(&mut result).expect("writing to Vec failed");
+            assert!(screen.is_active());
+            screen.write_all(b"foo").expect("writing to Vec failed");
+        }
+        assert_eq!(result, b"\x1b[?1049hfoo\x1b[?1049l");
+    }
+
+    #[test]
+    fn alternate_screen_leave() {
+        let screen = AlternateScreen::enter_unchecked(Vec::new()).expect("writing to Vec failed");
+        let result = screen.leave().expect("writing to Vec failed");
+        assert_eq!(result, b"\x1b[?1049h\x1b[?1049l");
+    }
+
+    #[test]
+    fn alternate_screen_drop_on_panic() {
+        let mut result = Vec::new();
+        let panicked = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            let _screen =
+                AlternateScreen::enter_unchecked(&mut result).expect("writing to Vec failed");
+            panic!("drawing failed");
+        }));
+        assert!(panicked.is_err());
+        assert_eq!(result, b"\x1b[?1049h\x1b[?1049l");
+    }
+}
@@ -0,0 +1,378 @@
+//! Truncation of text to a width.
+
+use std::{
+    borrow::Cow,
+    env,
+    path::{self, MAIN_SEPARATOR, MAIN_SEPARATOR_STR, Path},
+};
+
+use crate::{
+    is_sequence_start, skip_sequence,
+    width::{cluster_len, cluster_width, clusters, display_width},
+};
+
+/// Ellipsis that replaces the removed part of truncated text.
+const ELLIPSIS: &str = "…";
+
+/// Position at which text is removed when it is truncated.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum TruncationPosition {
+    /// The start of the text is removed and the tail is kept, e.g., `…/long/path/file.rs`.
+    Start,
+    /// The middle of the text is removed and the head and tail are kept, e.g., `driver…report.rs`.
+    Middle,
+    /// The end of the text is removed and the head is kept, e.g., `driver_report…`.
+    #[default]
+    End,
+}
+
+/// Returns the text truncated so that it occupies at most `width` columns.
+///
+/// If the text does not fit, the part at the specified position is replaced by an ellipsis (`…`).
+/// The result may occupy fewer than `width` columns if a wide character does not fit. Combining
+/// marks, variation selectors, and zero width joiners are never separated from the characters they
+/// modify or join. The text is borrowed if it fits.
+#[must_use]
+pub fn truncate(text: &str, width: usize, position: TruncationPosition) -> Cow<'_, str> {
+    if display_width(text) <= width {
+        return Cow::Borrowed(text);
+    }
+    // The ellipsis occupies one column.
+    let Some(available) = width.checked_sub(1) else {
+        return Cow::Borrowed("");
+    };
+
+    let (head_width, tail_width) = match position {
+        TruncationPosition::Start => (0, available),
+        TruncationPosition::Middle => (available.div_ceil(2), available / 2),
+        TruncationPosition::End => (available, 0),
+    };
+    let head = &text[..head_len(text, head_width)];
+    let tail = &text[text.len() - tail_len(text, tail_width)..];
+    Cow::Owned(format!("{head}{ELLIPSIS}{tail}"))
+}
+
+/// Returns the styled text truncated at the end so that it occupies at most `width` columns.
+///
+/// Unlike [`truncate`], this function is aware of ANSI escape sequences in the text: they do not
+/// count towards the width, and those after the truncation point are retained, so that the text
+/// ends in the same style state (e.g., reset) as the original. If the text does not fit, the
+/// removed characters are replaced by an ellipsis (`…`). The text is borrowed if it fits.
+///
+/// Like [`truncate`], this function does not separate combining marks, variation selectors, and
+/// zero width joiners from the characters they modify or join.
+#[must_use]
+pub fn truncate_styled(text: &str, width: usize) -> Cow<'_, str> {
+    if display_width(text) <= width {
+        return Cow::Borrowed(text);
+    }
+    // The ellipsis occupies one column.
+    let available = width.saturating_sub(1);
+    let mut truncated = String::with_capacity(text.len());
+    let mut current_width = 0;
+    let mut removed = false;
+    let mut rest = text;
+    while let Some(ch) = rest.chars().next() {
+        if is_sequence_start(ch) {
+            let after = skip_sequence(rest);
+            truncated.push_str(&rest[..rest.len() - after.len()]);
+            rest = after;
+            continue;
+        }
+        // Escape sequences start with a control character, which always starts a new cluster.
+        let (cluster, after) = rest.split_at(cluster_len(rest));
+        if !removed {
+            if current_width + cluster_width(cluster) <= available {
+                current_width += cluster_width(cluster);
+                truncated.push_str(cluster);
+            } else {
+                removed = true;
+                if width != 0 {
+                    truncated.push_str(ELLIPSIS);
+                }
+            }
+        }
+        rest = after;
+    }
+    Cow::Owned(truncated)
+}
+
+/// Returns the path shortened so that it occupies at most `width` columns, if possible.
+///
+/// The path is shortened in the following steps until it fits:
+///
+/// 1. The user's home directory is replaced by `~`.
+/// 2. Directory names are abbreviated to their first character (or their first two characters if
+///    they start with `.`), starting with the leftmost directory.
+/// 3. Directories are replaced by an ellipsis (`…`), starting with the leftmost directory.
+/// 4. The file name is returned on its own.
+///
+/// Path components are never split, so the result may occupy more than `width` columns if the file
+/// name is too long. Separators are normalized to the platform's main separator.
+#[must_use]
+pub fn shorten_path(path: &Path, width: usize) -> String {
+    let home = env::home_dir();
+    shorten_path_with_home(
+        &path.to_string_lossy(),
+        width,
+        home.as_deref().map(Path::to_string_lossy).as_deref(),
+    )
+}
+
+/// Returns the path shortened so that it occupies at most `width` columns, if possible, with the
+/// specified home directory replaced by `~`.
+#[must_use]
+fn shorten_path_with_home(path: &str, width: usize, home: Option<&str>) -> String {
+    // Returns the path represented by the components.
+    fn join(components: &[Cow<'_, str>]) -> String {
+        if let [root] = components
+            && root.is_empty()
+        {
+            MAIN_SEPARATOR_STR.to_owned()
+        } else {
+            components.join(MAIN_SEPARATOR_STR)
+        }
+    }
+
+    let mut components: Vec<Cow<'_, str>> = path
+        .trim_end_matches(path::is_separator)
+        .split(path::is_separator)
+        .map(Cow::Borrowed)
+        .collect();
+    if let Some(home) = home.map(|home| home.trim_end_matches(path::is_separator))
+        && !home.is_empty()
+    {
+        let home_len = home.split(path::is_separator).count();
+        if components.len() >= home_len
+            && components
+                .iter()
+                .zip(home.split(path::is_separator))
+                .all(|(component, home_component)| component == home_component)
+        {
+            components.splice(..home_len, [Cow::Borrowed("~")]);
+        }
+    }
+
+    let mut shortened = join(&components);
+    if display_width(&shortened) <= width || components.len() < 2 {
+        return shortened;
+    }
+
+    // Abbreviate directory names.
+    let last = components.len() - 1;
+    for index in 0..last {
+        let component = &components[index];
+        if matches!(component.as_ref(), "" | "~" | "." | "..") {
+            continue;
+        }
+        let abbreviated_len = component
+            .char_indices()
+            .nth(if component.starts_with('.') { 2 } else { 1 })
+            .map_or(component.len(), |(len, _)| len);
+        if abbreviated_len < component.len() {
+            components[index] = Cow::Owned(component[..abbreviated_len].to_owned());
+            shortened = join(&components);
+            if display_width(&shortened) <= width {
+                return shortened;
+            }
+        }
+    }
+
+    // Replace directories by an ellipsis.
+    let first = usize::from(matches!(components[0].as_ref(), "" | "~"));
+    for start in (first + 1)..=last {
+        let mut collapsed = components[..first].to_vec();
+        collapsed.push(Cow::Borrowed(ELLIPSIS));
+        collapsed.extend_from_slice(&components[start..]);
+        shortened = collapsed.join(MAIN_SEPARATOR_STR);
+        if display_width(&shortened) <= width {
+            return shortened;
+        }
+    }
+
+    let file_name = &components[last];
+    shortened = format!("{ELLIPSIS}{MAIN_SEPARATOR}{file_name}");
+    if display_width(&shortened) <= width {
+        shortened
+    } else {
+        file_name.clone().into_owned()
+    }
+}
+
+/// Returns the length in bytes of the longest prefix of the text that occupies at most `width`
+/// columns.
+#[must_use]
+fn head_len(text: &str, width: usize) -> usize {
+    let mut current_width = 0;
+    for (index, cluster) in clusters(text) {
+        current_width += cluster_width(cluster);
+        if current_width > width {
+            return index;
+        }
+    }
+    text.len()
+}
+
+/// Returns the length in bytes of the longest suffix of the text that occupies at most `width`
+/// columns.
+#[must_use]
+fn tail_len(text: &str, width: usize) -> usize {
+    let mut current_width = 0;
+    for (index, cluster) in clusters(text).into_iter().rev() {
+        current_width += cluster_width(cluster);
+        if current_width > width {
+            return text.len() - index - cluster.len();
+        }
+    }
+    text.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn truncate_fits() {
+        let result = truncate("driver_report.rs", 16, TruncationPosition::End);
+        assert!(matches!(result, Cow::Borrowed("driver_report.rs")));
+    }
+
+    #[test]
+    fn truncate_end() {
+        let result = truncate("driver_report.rs", 10, TruncationPosition::End);
+        assert_eq!(result, "driver_re…");
+    }
+
+    #[test]
+    fn truncate_start() {
+        let result = truncate("/very/long/path/file.rs", 16, TruncationPosition::Start);
+        assert_eq!(result, "…ng/path/file.rs");
+    }
+
+    #[test]
+    fn truncate_middle() {
+        let result = truncate("driver_final_report.rs", 16, TruncationPosition::Middle);
+        assert_eq!(result, "driver_f…port.rs");
+    }
+
+    #[test]
+    fn truncate_wide_characters() {
+        assert_eq!(
+            truncate("日本語テキスト", 6, TruncationPosition::End),
+            "日本…"
+        );
+        assert_eq!(
+            truncate("日本語テキスト", 6, TruncationPosition::Start),
+            "…スト"
+        );
+    }
+
+    #[test]
+    fn truncate_tiny_width() {
+        assert_eq!(truncate("foo", 1, TruncationPosition::Middle), "…");
+        assert_eq!(truncate("foo", 0, TruncationPosition::End), "");
+    }
+
+    /// Returns the path with `/` replaced by the platform's main separator.
+    fn native(path: &str) -> String {
+        path.replace('/', MAIN_SEPARATOR_STR)
+    }
+
+    #[test]
+    fn shorten_path_fits() {
+        let path = native("/usr/local/share/doc");
+        assert_eq!(shorten_path_with_home(&path, 20, None), path);
+        assert_eq!(shorten_path_with_home(&native("/"), 0, None), native("/"));
+    }
+
+    #[test]
+    fn shorten_path_home() {
+        let home = native("/home/user");
+        assert_eq!(
+            shorten_path_with_home(&native("/home/user/src/main.rs"), 80, Some(&home)),
+            native("~/src/main.rs")
+        );
+        assert_eq!(
+            shorten_path_with_home(&native("/home/user"), 80, Some(&home)),
+            "~"
+        );
+        assert_eq!(
+            shorten_path_with_home(&native("/home/username/x"), 80, Some(&home)),
+            native("/home/username/x")
+        );
+    }
+
+    #[test]
+    fn shorten_path_abbreviate() {
+        let path = native("/usr/share/local/.config/project/file.rs");
+        assert_eq!(
+            shorten_path_with_home(&path, 34, None),
+            native("/u/s/local/.config/project/file.rs")
+        );
+        assert_eq!(
+            shorten_path_with_home(&path, 20, None),
+            native("/u/s/l/.c/p/file.rs")
+        );
+    }
+
+    #[test]
+    fn shorten_path_ellipsis() {
+        let path = native("/usr/share/local/.config/project/file.rs");
+        assert_eq!(
+            shorten_path_with_home(&path, 16, None),
+            native("/…/.c/p/file.rs")
+        );
+        assert_eq!(
+            shorten_path_with_home(&native("~/a/b/c/file.rs"), 11, None),
+            native("~/…/file.rs")
+        );
+        assert_eq!(
+            shorten_path_with_home(&native("src/module/file.rs"), 9, None),
+            native("…/file.rs")
+        );
+    }
+
+    #[test]
+    fn shorten_path_file_name() {
+        assert_eq!(
+            shorten_path_with_home(&native("/usr/share/very_long_file_name.rs"), 10, None),
+            "very_long_file_name.rs"
+        );
+    }
+
+    #[test]
+    fn truncate_keeps_clusters() {
+        assert_eq!(
+            truncate("cafe\u{301}s!", 5, TruncationPosition::End),
+            "cafe\u{301}…"
+        );
+        assert_eq!(
+            truncate("a\u{301}bcde\u{301}", 2, TruncationPosition::Start),
+            "…e\u{301}"
+        );
+        assert_eq!(
+            truncate("ab👨\u{200d}👩cd", 5, TruncationPosition::End),
+            "ab👨\u{200d}👩…"
+        );
+        assert_eq!(
+            truncate_styled("\x1b[1mab👨\u{200d}👩cd\x1b[0m", 5),
+            "\x1b[1mab👨\u{200d}👩…\x1b[0m"
+        );
+        assert_eq!(truncate_styled("ae\u{301}bc", 3), "ae\u{301}…");
+    }
+
+    #[test]
+    fn truncate_styled_text() {
+        assert!(matches!(
+            truncate_styled("\x1b[1mfoo\x1b[0m", 3),
+            Cow::Borrowed("\x1b[1mfoo\x1b[0m")
+        ));
+        assert_eq!(
+            truncate_styled("\x1b[1mfoo\x1b[0m bar\x1b[31m baz\x1b[0m", 5),
+            "\x1b[1mfoo\x1b[0m …\x1b[31m\x1b[0m"
+        );
+        assert_eq!(truncate_styled("日本語", 4), "日…");
+        assert_eq!(truncate_styled("\x1b[1mfoo\x1b[0m", 0), "\x1b[1m\x1b[0m");
+    }
+}
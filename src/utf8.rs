@@ -0,0 +1,61 @@
+//! Incremental decoding of UTF-8 output.
+
+use core::str;
+
+/// Decoder of UTF-8 text that is written piecemeal, with characters possibly split across writes.
+#[derive(Debug, Default)]
+pub(crate) struct Utf8Decoder {
+    /// The bytes of an incomplete UTF-8 sequence at the end of the previous write.
+    pending: Vec<u8>,
+}
+
+impl Utf8Decoder {
+    /// Creates a decoder without pending bytes.
+    #[inline]
+    #[must_use]
+    pub(crate) const fn new() -> Self {
+        Self {
+            pending: Vec::new(),
+        }
+    }
+
+    /// Returns the text decoded from the pending bytes of the previous write and the bytes.
+    ///
+    /// Bytes that are not valid UTF-8 are replaced by U+FFFD. An incomplete UTF-8 sequence at the
+    /// end of the bytes is held back until the next write.
+    #[must_use]
+    pub(crate) fn decode(&mut self, bytes: &[u8]) -> String {
+        self.pending.extend_from_slice(bytes);
+        let mut text = String::with_capacity(self.pending.len());
+        let mut rest = self.pending.as_slice();
+        loop {
+            let error = str::from_utf8(rest).err();
+            let valid_len = error.map_or(rest.len(), |error| error.valid_up_to());
+            text.push_str(str::from_utf8(&rest[..valid_len]).unwrap_or_default());
+            rest = &rest[valid_len..];
+            let Some(invalid_len) = error.and_then(|error| error.error_len()) else {
+                // The remaining bytes, if any, are an incomplete UTF-8 sequence.
+                break;
+            };
+            text.push(char::REPLACEMENT_CHARACTER);
+            rest = &rest[invalid_len..];
+        }
+        let pending_len = rest.len();
+        self.pending.drain(..self.pending.len() - pending_len);
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_split_and_invalid() {
+        let mut decoder = Utf8Decoder::new();
+        assert_eq!(decoder.decode(b"a\xc3"), "a");
+        assert_eq!(decoder.decode(b"\xa4\xff b"), "ä\u{fffd} b");
+        assert_eq!(decoder.decode(b"\xe2\x82"), "");
+        assert_eq!(decoder.decode(b"\xacx"), "€x");
+    }
+}
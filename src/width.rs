@@ -0,0 +1,157 @@
+//! Display width of text.
+
+use unicode_width::{UnicodeWidthChar as _, UnicodeWidthStr as _};
+
+use crate::strip_ansi;
+
+/// Zero width joiner, which joins the adjacent characters into a single glyph, e.g., in emoji
+/// sequences.
+const ZERO_WIDTH_JOINER: char = '\u{200d}';
+
+/// Returns the number of terminal columns occupied by the text.
+///
+/// The width is measured according to the Unicode width rules: wide characters (e.g., most Chinese
+/// and Japanese characters and emoji) occupy two columns, combining marks and zero width joiners do
+/// not occupy any columns, and emoji sequences joined by zero width joiners occupy the columns of a
+/// single emoji. ANSI escape sequences and control characters, including tabs and line feeds, do
+/// not occupy any columns, so tabs must be expanded before text containing them is measured. This
+/// differs from the number of bytes or characters of text that is not plain ASCII.
+#[must_use]
+pub fn display_width(text: &str) -> usize {
+    text_width(&strip_ansi(text))
+}
+
+/// Returns the number of terminal columns occupied by the text, which must not contain ANSI escape
+/// sequences.
+///
+/// Control characters do not occupy any columns, as in [`char_width`].
+#[must_use]
+fn text_width(text: &str) -> usize {
+    text.split(char::is_control).map(str::width).sum()
+}
+
+/// Returns the number of terminal columns occupied by the character.
+///
+/// Control characters do not occupy any columns.
+#[inline]
+#[must_use]
+pub(crate) fn char_width(ch: char) -> usize {
+    ch.width().unwrap_or(0)
+}
+
+/// Returns the length in bytes of the first cluster of the text, i.e., of its first character
+/// together with the characters that must not be separated from it.
+///
+/// These are zero-width characters that modify the preceding character (e.g., combining marks,
+/// variation selectors, and zero width joiners), and characters joined to the preceding character
+/// by a zero width joiner. Control characters always start a new cluster.
+#[must_use]
+pub(crate) fn cluster_len(text: &str) -> usize {
+    let mut chars = text.char_indices();
+    let Some((_, mut previous)) = chars.next() else {
+        return 0;
+    };
+    for (index, ch) in chars {
+        if ch.is_control() || (previous != ZERO_WIDTH_JOINER && char_width(ch) != 0) {
+            return index;
+        }
+        previous = ch;
+    }
+    text.len()
+}
+
+/// Returns the clusters of the text, as determined by [`cluster_len`], together with their byte
+/// indices.
+#[must_use]
+pub(crate) fn clusters(text: &str) -> Vec<(usize, &str)> {
+    let mut clusters = Vec::new();
+    let mut index = 0;
+    while index < text.len() {
+        let len = cluster_len(&text[index..]);
+        clusters.push((index, &text[index..index + len]));
+        index += len;
+    }
+    clusters
+}
+
+/// Returns the number of terminal columns occupied by the cluster, which must not contain ANSI
+/// escape sequences.
+#[inline]
+#[must_use]
+pub(crate) fn cluster_width(cluster: &str) -> usize {
+    text_width(cluster)
+}
+
+/// Returns the byte index at which the text must be split so that the first part occupies at most
+/// `width` columns.
+///
+/// The text is only split between [clusters](cluster_len), so that, e.g., combining marks are not
+/// separated from their base character. The first part contains at least one cluster, even if it
+/// occupies more than `width` columns, unless the text is empty.
+#[must_use]
+pub(crate) fn split_index_at_width(text: &str, width: usize) -> usize {
+    let mut current_width = 0;
+    for (index, cluster) in clusters(text) {
+        current_width += cluster_width(cluster);
+        if current_width > width && index != 0 {
+            return index;
+        }
+    }
+    text.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_width_ascii() {
+        assert_eq!(display_width(""), 0);
+        assert_eq!(display_width("foo bar"), 7);
+    }
+
+    #[test]
+    fn display_width_wide_and_combining() {
+        assert_eq!(display_width("日本語"), 6);
+        assert_eq!(display_width("e\u{301}"), 1);
+        assert_eq!(display_width("👨\u{200d}👩\u{200d}👧"), 2);
+    }
+
+    #[test]
+    fn display_width_ansi() {
+        assert_eq!(display_width("\x1b[1;31mfoo\x1b[0m"), 3);
+    }
+
+    #[test]
+    fn display_width_control_characters() {
+        assert_eq!(display_width("a\tb\r\n"), 2);
+        assert_eq!(display_width("\x07"), 0);
+    }
+
+    #[test]
+    fn split_index() {
+        assert_eq!(split_index_at_width("foobar", 4), 4);
+        assert_eq!(split_index_at_width("foo", 4), 3);
+        assert_eq!(split_index_at_width("日本語", 3), 3);
+        assert_eq!(split_index_at_width("日本語", 1), 3);
+        assert_eq!(split_index_at_width("", 1), 0);
+    }
+
+    #[test]
+    fn split_index_keeps_clusters() {
+        assert_eq!(split_index_at_width("ae\u{301}b", 1), 1);
+        assert_eq!(split_index_at_width("ae\u{301}b", 2), 4);
+        assert_eq!(split_index_at_width("\u{2764}\u{fe0f}x", 1), 6);
+        assert_eq!(split_index_at_width("👨\u{200d}👩x", 2), 11);
+        assert_eq!(split_index_at_width("👨\u{200d}👩x", 3), 12);
+    }
+
+    #[test]
+    fn cluster_boundaries() {
+        assert_eq!(
+            clusters("e\u{301}👨\u{200d}👩\x1b\u{301}"),
+            [(0, "e\u{301}"), (3, "👨\u{200d}👩"), (14, "\x1b\u{301}")]
+        );
+        assert_eq!(cluster_len(""), 0);
+    }
+}
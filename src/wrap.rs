@@ -0,0 +1,410 @@
+//! Text wrapping.
+
+use core::ops::Range;
+use std::borrow::Cow;
+
+use crate::{
+    RESET_STYLE, Style, is_sequence_start, skip_sequence,
+    width::{char_width, display_width, split_index_at_width},
+};
+
+/// Characters that must not start a line in Chinese or Japanese text, e.g., closing brackets and
+/// small kana.
+const NO_BREAK_BEFORE: &str = "!),.:;?]}»、。々〉》」』】〕〗〙〟ぁぃぅぇぉっゃゅょゎゝゞァィゥェォッャュョヮヵヶ・ーヽヾ！），．：；？］｝｠’”";
+
+/// Characters that must not end a line in Chinese or Japanese text, e.g., opening brackets.
+const NO_BREAK_AFTER: &str = "([{«〈《「『【〔〖〘〝（［｛｟‘“";
+
+/// Options for [wrapping](wrap_with) text.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[expect(clippy::exhaustive_structs)]
+pub struct WrapOptions<'a> {
+    /// The maximum number of columns occupied by a line, including its indentation.
+    pub width: usize,
+    /// The text that precedes the first line, e.g., `"- "`.
+    pub initial_indent: &'a str,
+    /// The text that precedes all lines except the first, e.g., `"  "`.
+    pub subsequent_indent: &'a str,
+}
+
+impl WrapOptions<'_> {
+    /// Returns options for wrapping text to the specified width without indentation.
+    #[inline]
+    #[must_use]
+    pub const fn new(width: usize) -> Self {
+        Self {
+            width,
+            initial_indent: "",
+            subsequent_indent: "",
+        }
+    }
+}
+
+/// Wraps text so that each resulting line occupies at most `width` columns, and returns the
+/// resulting lines.
+///
+/// Each line of the text is wrapped separately. Lines are broken at spaces, which are removed at
+/// the line breaks, and between Chinese or Japanese characters, except before closing punctuation
+/// and after opening punctuation. Words that are longer than `width` are broken between characters,
+/// but never between a character and its combining marks. Leading spaces of each line of the text
+/// are retained. A `width` of zero is treated as one.
+#[must_use]
+pub fn wrap(text: &str, width: usize) -> Vec<Cow<'_, str>> {
+    wrap_with(text, &WrapOptions::new(width))
+}
+
+/// Wraps text as [`wrap`] does, with the specified options, and returns the resulting lines.
+///
+/// The indentation counts towards the width, but the text on each line occupies at least one
+/// column, even if the indentation does not leave room for it. Empty lines are not indented. The
+/// lines are borrowed from the text unless they are indented.
+#[must_use]
+pub fn wrap_with<'a>(text: &'a str, options: &WrapOptions<'_>) -> Vec<Cow<'a, str>> {
+    // Returns the line preceded by the indentation.
+    fn indent<'a>(indent: &str, line: &'a str) -> Cow<'a, str> {
+        if indent.is_empty() || line.is_empty() {
+            Cow::Borrowed(line)
+        } else {
+            Cow::Owned(format!("{indent}{line}"))
+        }
+    }
+
+    let initial_width = options
+        .width
+        .saturating_sub(display_width(options.initial_indent));
+    let subsequent_width = options
+        .width
+        .saturating_sub(display_width(options.subsequent_indent));
+    let mut lines = Vec::new();
+    for (index, line) in text.split('\n').enumerate() {
+        let mut rest = line;
+        if index == 0 && initial_width != subsequent_width {
+            let first = wrap_line(line, initial_width)[0];
+            lines.push(indent(options.initial_indent, first));
+            rest = line[first.len()..].trim_start_matches(' ');
+            if rest.is_empty() {
+                continue;
+            }
+        }
+        for wrapped in wrap_line(rest, subsequent_width) {
+            let indentation = if lines.is_empty() {
+                options.initial_indent
+            } else {
+                options.subsequent_indent
+            };
+            lines.push(indent(indentation, wrapped));
+        }
+    }
+    lines
+}
+
+/// Wraps styled text as [`wrap`] does, and returns the resulting lines.
+///
+/// Unlike [`wrap`], this function is aware of ANSI escape sequences in the text: they do not count
+/// towards the width. Each resulting line is styled independently: the style that is active at the
+/// start of a line is set at its start, and the style is reset at its end if it is not the default
+/// style, so that the lines can be written individually, e.g., next to other text.
+#[must_use]
+pub fn wrap_styled(text: &str, width: usize) -> Vec<String> {
+    // Separate the text into the plain text and the escape sequences with their positions in it.
+    let mut plain = String::with_capacity(text.len());
+    let mut sequences = Vec::new();
+    let mut rest = text;
+    while let Some(pos) = rest.find(is_sequence_start) {
+        plain.push_str(&rest[..pos]);
+        let after = skip_sequence(&rest[pos..]);
+        sequences.push((plain.len(), &rest[pos..rest.len() - after.len()]));
+        rest = after;
+    }
+    plain.push_str(rest);
+
+    let mut ranges = Vec::new();
+    let mut line_start = 0;
+    for line in plain.split('\n') {
+        ranges.extend(
+            wrap_line_ranges(line, width)
+                .into_iter()
+                .map(|range| line_start + range.start..line_start + range.end),
+        );
+        line_start += line.len() + 1;
+    }
+
+    let mut lines = Vec::with_capacity(ranges.len());
+    let mut style = Style::default();
+    let mut sequences = sequences.into_iter().peekable();
+    let mut buffer = Style::new_set_style_buffer();
+    for (index, range) in ranges.iter().enumerate() {
+        let is_last = index + 1 == ranges.len();
+        let mut line = String::from(style.set_style(&mut buffer));
+        let mut pos = range.start;
+        // Escape sequences in the spaces removed at a line break are written at the start of the
+        // next line, and those after the last line at its end.
+        while let Some(&(sequence_pos, sequence)) = sequences.peek() {
+            if sequence_pos > range.end && !is_last {
+                break;
+            }
+            let sequence_pos = sequence_pos.clamp(pos, range.end);
+            line.push_str(&plain[pos..sequence_pos]);
+            line.push_str(sequence);
+            pos = sequence_pos;
+            if let Some(parameters) = sequence
+                .strip_prefix("\x1b[")
+                .and_then(|sequence| sequence.strip_suffix('m'))
+            {
+                style = style.with_sgr_parameters(parameters);
+            }
+            sequences.next();
+        }
+        line.push_str(&plain[pos..range.end]);
+        if style != Style::default() {
+            line.push_str(RESET_STYLE);
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+/// Wraps a line of text so that each resulting line occupies at most `width` columns, and returns
+/// the resulting lines.
+///
+/// Lines are broken at spaces, which are removed at the line breaks, and between Chinese or
+/// Japanese characters, except before closing punctuation and after opening punctuation. Words that
+/// are longer than `width` are broken at character boundaries. Leading spaces of the text are
+/// retained on the first line. A `width` of zero is treated as one.
+#[must_use]
+pub(crate) fn wrap_line(line: &str, width: usize) -> Vec<&str> {
+    wrap_line_ranges(line, width)
+        .into_iter()
+        .map(|range| &line[range])
+        .collect()
+}
+
+/// Wraps a line of text as [`wrap_line`] does, and returns the byte ranges of the resulting lines.
+#[must_use]
+fn wrap_line_ranges(line: &str, width: usize) -> Vec<Range<usize>> {
+    let width = width.max(1);
+    let mut lines = Vec::new();
+    // Start of the current line, or `None` if no word has been placed on it.
+    let mut start = None;
+    // End of the last word placed on the current line.
+    let mut end = 0;
+    // Number of columns occupied by the current line.
+    let mut current_width = 0;
+
+    for (gap_start, word_start, word_end) in words(line) {
+        let word_width = display_width(&line[word_start..word_end]);
+        let mut line_start = match start {
+            None if lines.is_empty() => {
+                current_width = display_width(&line[gap_start..word_start]) + word_width;
+                gap_start
+            }
+            None => {
+                current_width = word_width;
+                word_start
+            }
+            Some(line_start) => {
+                let gap_width = display_width(&line[gap_start..word_start]);
+                if current_width + gap_width + word_width <= width {
+                    current_width += gap_width + word_width;
+                    line_start
+                } else {
+                    lines.push(line_start..end);
+                    current_width = word_width;
+                    word_start
+                }
+            }
+        };
+
+        while current_width > width {
+            let split = line_start + split_index_at_width(&line[line_start..word_end], width);
+            if split == word_end {
+                break;
+            }
+            lines.push(line_start..split);
+            line_start = split;
+            current_width = display_width(&line[line_start..word_end]);
+        }
+
+        start = Some(line_start);
+        end = word_end;
+    }
+
+    match start {
+        Some(line_start) => lines.push(line_start..end),
+        None if lines.is_empty() => lines.push(0..0),
+        None => {}
+    }
+    lines
+}
+
+/// Returns an iterator over the words in the text, each represented by the start of the spaces
+/// preceding the word, the start of the word, and the end of the word.
+///
+/// Each Chinese or Japanese character is a word on its own, unless it is attached to an adjacent
+/// character by the line-breaking rules for these languages.
+fn words(text: &str) -> impl Iterator<Item = (usize, usize, usize)> {
+    let mut pos = 0;
+    core::iter::from_fn(move || {
+        let gap_start = pos;
+        let word_start = text[pos..]
+            .find(|ch| ch != ' ')
+            .map_or(text.len(), |index| pos + index);
+        let mut previous = None;
+        let word_end = text[word_start..]
+            .char_indices()
+            .find(|&(_, ch)| {
+                let is_end = ch == ' ' || previous.is_some_and(|previous| is_break(previous, ch));
+                previous = Some(ch);
+                is_end
+            })
+            .map_or(text.len(), |(index, _)| word_start + index);
+        pos = word_end;
+        (word_start < word_end).then_some((gap_start, word_start, word_end))
+    })
+}
+
+/// Returns whether a line may be broken between the specified adjacent characters, which are not
+/// spaces.
+#[must_use]
+fn is_break(previous: char, next: char) -> bool {
+    (is_cjk(previous) || is_cjk(next))
+        && char_width(next) != 0
+        && previous != '\u{200d}'
+        && !NO_BREAK_BEFORE.contains(next)
+        && !NO_BREAK_AFTER.contains(previous)
+}
+
+/// Returns whether the character is a Chinese or Japanese character or punctuation mark, between
+/// which lines may be broken without spaces.
+#[inline]
+#[must_use]
+const fn is_cjk(ch: char) -> bool {
+    matches!(
+        ch,
+        '\u{2e80}'..='\u{2fff}'
+            | '\u{3000}'..='\u{30ff}'
+            | '\u{31c0}'..='\u{31ff}'
+            | '\u{3400}'..='\u{4dbf}'
+            | '\u{4e00}'..='\u{9fff}'
+            | '\u{f900}'..='\u{faff}'
+            | '\u{ff00}'..='\u{ffef}'
+            | '\u{20000}'..='\u{3ffff}'
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wrap_line_empty() {
+        assert_eq!(wrap_line("", 10), [""]);
+        assert_eq!(wrap_line("   ", 10), [""]);
+    }
+
+    #[test]
+    fn wrap_line_fits() {
+        assert_eq!(wrap_line("foo bar", 7), ["foo bar"]);
+    }
+
+    #[test]
+    fn wrap_line_words() {
+        assert_eq!(
+            wrap_line("the quick brown fox jumps", 10),
+            ["the quick", "brown fox", "jumps"]
+        );
+    }
+
+    #[test]
+    fn wrap_line_spaces() {
+        assert_eq!(
+            wrap_line("  indented  text with  spaces  ", 12),
+            ["  indented", "text with", "spaces"]
+        );
+    }
+
+    #[test]
+    fn wrap_line_long_word() {
+        assert_eq!(
+            wrap_line("a verylongword b", 5),
+            ["a", "veryl", "ongwo", "rd b"]
+        );
+    }
+
+    #[test]
+    fn wrap_line_wide_characters() {
+        assert_eq!(wrap_line("日本語 テキスト", 7), ["日本語", "テキス", "ト"]);
+    }
+
+    #[test]
+    fn wrap_line_cjk() {
+        assert_eq!(
+            wrap_line("これは日本語の文章です。", 8),
+            ["これは日", "本語の文", "章です。"]
+        );
+        assert_eq!(
+            wrap_line("「東京」へ行った。", 6),
+            ["「東", "京」へ", "行っ", "た。"]
+        );
+        assert_eq!(wrap_line("Rust言語", 5), ["Rust", "言語"]);
+    }
+
+    #[test]
+    fn wrap_text() {
+        assert_eq!(
+            wrap("the quick brown fox\n\n  jumps over", 10),
+            ["the quick", "brown fox", "", "  jumps", "over"]
+        );
+        assert!(matches!(wrap("foo", 5)[0], Cow::Borrowed("foo")));
+    }
+
+    #[test]
+    fn wrap_with_indent() {
+        let hanging = WrapOptions {
+            width: 12,
+            initial_indent: "- ",
+            subsequent_indent: "    ",
+        };
+        assert_eq!(
+            wrap_with("the quick brown fox jumps\nover", &hanging),
+            [
+                "- the quick",
+                "    brown",
+                "    fox",
+                "    jumps",
+                "    over"
+            ]
+        );
+        let uniform = WrapOptions {
+            width: 12,
+            initial_indent: "  ",
+            subsequent_indent: "  ",
+        };
+        assert_eq!(
+            wrap_with("the quick brown\n\nfox", &uniform),
+            ["  the quick", "  brown", "", "  fox"]
+        );
+    }
+
+    #[test]
+    fn wrap_styled_text() {
+        assert_eq!(
+            wrap_styled("the \x1b[1mquick brown\x1b[0m fox", 10),
+            ["the \x1b[1mquick\x1b[0m", "\x1b[1mbrown\x1b[0m fox"]
+        );
+        assert_eq!(
+            wrap_styled("\x1b[31mfoo bar\nbaz", 3),
+            [
+                "\x1b[31mfoo\x1b[0m",
+                "\x1b[31mbar\x1b[0m",
+                "\x1b[31mbaz\x1b[0m"
+            ]
+        );
+        assert_eq!(wrap_styled("plain text", 5), ["plain", "text"]);
+    }
+
+    #[test]
+    fn wrap_line_zero_width() {
+        assert_eq!(wrap_line("ab c", 0), ["a", "b", "c"]);
+    }
+}
@@ -0,0 +1,1359 @@
+//! Adapters that transform styled output on its way to a stream.
+
+use core::mem;
+use std::io::{self, Write};
+
+use crate::{
+    RESET_STYLE, Style, StyledDisplay,
+    guard::{Finish, FinishOnDrop},
+    is_sequence_start, skip_sequence, truncate_styled,
+    utf8::Utf8Decoder,
+    width::{clusters, display_width},
+    wrap::wrap_line,
+};
+
+/// Number of columns between the tab stops of a terminal.
+const TAB_STOP: usize = 8;
+
+/// Character encoding of the output written to a stream.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum OutputEncoding {
+    /// UTF-8. Bytes are passed through untouched, even if they are not valid UTF-8.
+    #[default]
+    Utf8,
+    /// ISO 8859-1 (Latin-1), which encodes the characters up to U+00FF in one byte each.
+    Latin1,
+    /// ASCII, which encodes the characters up to U+007F in one byte each.
+    Ascii,
+}
+
+impl OutputEncoding {
+    /// Returns the greatest character that can be represented in the encoding.
+    #[inline]
+    #[must_use]
+    const fn max_char(self) -> char {
+        match self {
+            Self::Utf8 => char::MAX,
+            Self::Latin1 => '\u{ff}',
+            Self::Ascii => '\u{7f}',
+        }
+    }
+}
+
+/// Adapter that transcodes UTF-8 output to another character encoding, for legacy consoles and
+/// serial devices.
+///
+/// Characters that cannot be represented in the encoding, as well as bytes that are not valid
+/// UTF-8, are replaced by `?`. ANSI escape sequences consist of ASCII characters, so they are
+/// written unchanged. A character that is split across multiple writes is transcoded when its last
+/// byte is written; an incomplete character is discarded if the adapter is dropped or
+/// [unwrapped](Self::into_inner) before then.
+#[derive(Debug)]
+pub struct TranscodingWriter<W>
+where
+    W: Write,
+{
+    /// The stream to which the transcoded output is written.
+    stream: W,
+    /// The encoding of the output.
+    encoding: OutputEncoding,
+    /// The decoder of the output, which holds back an incomplete UTF-8 sequence at the end of the
+    /// previous write.
+    decoder: Utf8Decoder,
+}
+
+impl<W> TranscodingWriter<W>
+where
+    W: Write,
+{
+    /// Creates an adapter that transcodes output to the specified encoding before writing it to
+    /// `stream`.
+    #[inline]
+    #[must_use]
+    pub const fn new(stream: W, encoding: OutputEncoding) -> Self {
+        Self {
+            stream,
+            encoding,
+            decoder: Utf8Decoder::new(),
+        }
+    }
+
+    /// Returns the encoding of the output.
+    #[inline]
+    #[must_use]
+    pub const fn encoding(&self) -> OutputEncoding {
+        self.encoding
+    }
+
+    /// Returns a reference to the stream.
+    #[inline]
+    #[must_use]
+    pub const fn get_ref(&self) -> &W {
+        &self.stream
+    }
+
+    /// Returns a mutable reference to the stream.
+    #[inline]
+    #[must_use]
+    pub const fn get_mut(&mut self) -> &mut W {
+        &mut self.stream
+    }
+
+    /// Returns the stream.
+    #[inline]
+    #[must_use]
+    pub fn into_inner(self) -> W {
+        self.stream
+    }
+}
+
+impl<W> Write for TranscodingWriter<W>
+where
+    W: Write,
+{
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.encoding == OutputEncoding::Utf8 {
+            return self.stream.write(buf);
+        }
+
+        // Invalid UTF-8 is decoded as U+FFFD, which cannot be represented in the other encodings.
+        let max_char = self.encoding.max_char();
+        let transcoded: Vec<u8> = self
+            .decoder
+            .decode(buf)
+            .chars()
+            .map(|ch| {
+                u8::try_from(ch)
+                    .ok()
+                    .filter(|_| ch <= max_char)
+                    .unwrap_or(b'?')
+            })
+            .collect();
+        self.stream.write_all(&transcoded)?;
+        Ok(buf.len())
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        self.stream.flush()
+    }
+}
+
+/// Adapter that treats a broken pipe as a signal to stop writing rather than as an error.
+///
+/// When the reading end of a pipe is closed, e.g., because the output is piped into `head`, writes
+/// to the stream fail with [`io::ErrorKind::BrokenPipe`]. This adapter reports such a write as
+/// successful and silently discards all further output, so that the program does not fail or
+/// print error messages for output that nobody reads. [`is_broken`](Self::is_broken) tells whether
+/// this has happened, so that the program can stop producing output early.
+#[derive(Debug)]
+pub struct BrokenPipeGuard<W>
+where
+    W: Write,
+{
+    /// The stream to which output is written.
+    stream: W,
+    /// Whether a write to the stream has failed because of a broken pipe.
+    broken: bool,
+}
+
+impl<W> BrokenPipeGuard<W>
+where
+    W: Write,
+{
+    /// Creates an adapter that writes output to `stream` until the pipe is broken.
+    #[inline]
+    #[must_use]
+    pub const fn new(stream: W) -> Self {
+        Self {
+            stream,
+            broken: false,
+        }
+    }
+
+    /// Returns whether a write to the stream has failed because of a broken pipe.
+    #[inline]
+    #[must_use]
+    pub const fn is_broken(&self) -> bool {
+        self.broken
+    }
+
+    /// Returns a reference to the stream.
+    #[inline]
+    #[must_use]
+    pub const fn get_ref(&self) -> &W {
+        &self.stream
+    }
+
+    /// Returns a mutable reference to the stream.
+    #[inline]
+    #[must_use]
+    pub const fn get_mut(&mut self) -> &mut W {
+        &mut self.stream
+    }
+
+    /// Returns the stream.
+    #[inline]
+    #[must_use]
+    pub fn into_inner(self) -> W {
+        self.stream
+    }
+
+    /// Returns the result of an operation on the stream, with a broken pipe error replaced by the
+    /// specified value.
+    fn handle<T>(&mut self, result: io::Result<T>, value: T) -> io::Result<T> {
+        match result {
+            Err(error) if error.kind() == io::ErrorKind::BrokenPipe => {
+                self.broken = true;
+                Ok(value)
+            }
+            result => result,
+        }
+    }
+}
+
+impl<W> Write for BrokenPipeGuard<W>
+where
+    W: Write,
+{
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.broken {
+            return Ok(buf.len());
+        }
+        let result = self.stream.write(buf);
+        self.handle(result, buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if self.broken {
+            return Ok(());
+        }
+        let result = self.stream.flush();
+        self.handle(result, ())
+    }
+}
+
+/// Adapter that tracks the column at which the next character written to a stream is displayed.
+///
+/// The column is determined from all output written since the last line feed or carriage return,
+/// so it is correct even if a line is written piecemeal, with characters or ANSI escape sequences
+/// split across writes. Escape sequences do not occupy any columns, wide characters occupy two
+/// columns, and tab stops are assumed every eight columns. The column is counted from 0.
+#[derive(Debug)]
+pub struct ColumnTrackingWriter<W>
+where
+    W: Write,
+{
+    /// The stream to which output is written.
+    stream: W,
+    /// The decoder of the output.
+    decoder: Utf8Decoder,
+    /// The column after the output written since the last line feed or carriage return, except
+    /// for the pending text.
+    column: usize,
+    /// The text at the end of the output that may be continued by the next write: an escape
+    /// sequence that may be incomplete, or the last cluster of the line.
+    pending: String,
+}
+
+impl<W> ColumnTrackingWriter<W>
+where
+    W: Write,
+{
+    /// Creates an adapter that writes output to `stream`, assuming that the stream is at the start
+    /// of a line.
+    #[inline]
+    #[must_use]
+    pub const fn new(stream: W) -> Self {
+        Self {
+            stream,
+            decoder: Utf8Decoder::new(),
+            column: 0,
+            pending: String::new(),
+        }
+    }
+
+    /// Returns the column at which the next character written to the stream is displayed.
+    #[inline]
+    #[must_use]
+    pub fn column(&self) -> usize {
+        column_after(self.column, &self.pending)
+    }
+
+    /// Advances the column past the pending text, except for the text that may be continued by the
+    /// next write.
+    fn advance(&mut self) {
+        let mut rest = self.pending.as_str();
+        let held_back_len = loop {
+            let Some(pos) = rest.find(is_sequence_start) else {
+                let index = clusters(rest).last().map_or(0, |&(index, _)| index);
+                self.column = column_after(self.column, &rest[..index]);
+                break rest.len() - index;
+            };
+            self.column = column_after(self.column, &rest[..pos]);
+            let after = skip_sequence(&rest[pos..]);
+            if after.is_empty() {
+                break rest.len() - pos;
+            }
+            rest = after;
+        };
+        self.pending.drain(..self.pending.len() - held_back_len);
+    }
+
+    /// Returns a reference to the stream.
+    #[inline]
+    #[must_use]
+    pub const fn get_ref(&self) -> &W {
+        &self.stream
+    }
+
+    /// Returns a mutable reference to the stream.
+    ///
+    /// Output that is written directly to the stream is not tracked.
+    #[inline]
+    #[must_use]
+    pub const fn get_mut(&mut self) -> &mut W {
+        &mut self.stream
+    }
+
+    /// Returns the stream.
+    #[inline]
+    #[must_use]
+    pub fn into_inner(self) -> W {
+        self.stream
+    }
+}
+
+impl<W> Write for ColumnTrackingWriter<W>
+where
+    W: Write,
+{
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let len = self.stream.write(buf)?;
+        let text = self.decoder.decode(&buf[..len]);
+        if let Some(pos) = text.rfind(['\n', '\r']) {
+            self.column = 0;
+            self.pending.clear();
+            self.pending.push_str(&text[pos + 1..]);
+        } else {
+            self.pending.push_str(&text);
+        }
+        self.advance();
+        Ok(len)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        self.stream.flush()
+    }
+}
+
+/// Returns the column at which the next character is displayed after the text, which must not
+/// contain line feeds or carriage returns, is displayed starting at `column`.
+#[must_use]
+fn column_after(column: usize, text: &str) -> usize {
+    text.split('\t')
+        .enumerate()
+        .fold(column, |column, (index, part)| {
+            let column = if index == 0 {
+                column
+            } else {
+                (column / TAB_STOP + 1) * TAB_STOP
+            };
+            column + display_width(part)
+        })
+}
+
+/// Adapter that collapses runs of consecutive blank lines to a maximum number of blank lines.
+///
+/// A line is blank if it contains only spaces, tabs, and carriage returns. Such characters at the
+/// start of a line are held back until the rest of the line is written, and they are discarded
+/// along with the line if it is a blank line that exceeds the maximum. Blank lines at the start of
+/// the output count as a run as well.
+#[derive(Debug)]
+pub struct BlankLineCollapser<W>
+where
+    W: Write,
+{
+    /// The stream to which output is written.
+    stream: W,
+    /// The maximum number of consecutive blank lines.
+    max_blank_lines: usize,
+    /// The number of consecutive blank lines written since the last line that is not blank.
+    blank_lines: usize,
+    /// Whether no character other than whitespace has been written since the last line feed.
+    at_line_start: bool,
+    /// The whitespace written since the last line feed, which has been held back.
+    pending: Vec<u8>,
+}
+
+impl<W> BlankLineCollapser<W>
+where
+    W: Write,
+{
+    /// Creates an adapter that writes output to `stream` with at most `max_blank_lines`
+    /// consecutive blank lines.
+    #[inline]
+    #[must_use]
+    pub const fn new(stream: W, max_blank_lines: usize) -> Self {
+        Self {
+            stream,
+            max_blank_lines,
+            blank_lines: 0,
+            at_line_start: true,
+            pending: Vec::new(),
+        }
+    }
+
+    /// Returns a reference to the stream.
+    #[inline]
+    #[must_use]
+    pub const fn get_ref(&self) -> &W {
+        &self.stream
+    }
+
+    /// Returns a mutable reference to the stream.
+    #[inline]
+    #[must_use]
+    pub const fn get_mut(&mut self) -> &mut W {
+        &mut self.stream
+    }
+
+    /// Returns the stream. Whitespace that has been held back is discarded.
+    #[inline]
+    #[must_use]
+    pub fn into_inner(self) -> W {
+        self.stream
+    }
+}
+
+impl<W> Write for BlankLineCollapser<W>
+where
+    W: Write,
+{
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut output = Vec::with_capacity(buf.len());
+        for &byte in buf {
+            match byte {
+                b'\n' if self.at_line_start => {
+                    if self.blank_lines < self.max_blank_lines {
+                        output.append(&mut self.pending);
+                        output.push(byte);
+                        self.blank_lines += 1;
+                    } else {
+                        self.pending.clear();
+                    }
+                }
+                b'\n' => {
+                    output.push(byte);
+                    self.at_line_start = true;
+                    self.blank_lines = 0;
+                }
+                b' ' | b'\t' | b'\r' if self.at_line_start => self.pending.push(byte),
+                _ => {
+                    output.append(&mut self.pending);
+                    output.push(byte);
+                    self.at_line_start = false;
+                }
+            }
+        }
+        self.stream.write_all(&output)?;
+        Ok(buf.len())
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        self.stream.flush()
+    }
+}
+
+/// Adapter that replaces consecutive identical lines with a summary, e.g., `last message repeated 3
+/// times`.
+///
+/// The first of the identical lines is written, and the repetitions are replaced by a summary line
+/// in [`summary_style`](Self::new) that is written before the next different line. Lines are held
+/// back until they are terminated by a line feed, and the summary until the next different line is
+/// written, so that repetitions can be detected. [`finish`](Self::finish) writes output that has
+/// been held back; this also happens when the adapter is dropped, ignoring errors.
+#[derive(Debug)]
+pub struct RepeatedLineSuppressor<W>
+where
+    W: Write,
+{
+    /// The stream to which output is written, and the output that has been held back.
+    inner: FinishOnDrop<W, RepeatedLines>,
+}
+
+/// State of a [`RepeatedLineSuppressor`].
+#[derive(Debug)]
+struct RepeatedLines {
+    /// The style of the summary line.
+    summary_style: Style,
+    /// The bytes of the current line, which has not been terminated yet.
+    line: Vec<u8>,
+    /// The bytes of the last line written, without the line feed.
+    previous: Option<Vec<u8>>,
+    /// The number of repetitions of the last line written that have been suppressed.
+    repetitions: usize,
+}
+
+impl<W> RepeatedLineSuppressor<W>
+where
+    W: Write,
+{
+    /// Creates an adapter that writes output to `stream`, with repeated lines replaced by a summary
+    /// line in the specified style.
+    #[inline]
+    #[must_use]
+    pub const fn new(stream: W, summary_style: Style) -> Self {
+        Self {
+            inner: FinishOnDrop::new(
+                stream,
+                RepeatedLines {
+                    summary_style,
+                    line: Vec::new(),
+                    previous: None,
+                    repetitions: 0,
+                },
+            ),
+        }
+    }
+
+    /// Returns a reference to the stream.
+    #[inline]
+    #[must_use]
+    pub const fn get_ref(&self) -> &W {
+        &self.inner.stream
+    }
+
+    /// Returns a mutable reference to the stream.
+    #[inline]
+    #[must_use]
+    pub const fn get_mut(&mut self) -> &mut W {
+        &mut self.inner.stream
+    }
+
+    /// Writes output that has been held back and returns the stream.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing to or flushing the stream fails.
+    #[inline]
+    pub fn finish(self) -> io::Result<W> {
+        self.inner.into_stream()
+    }
+}
+
+impl RepeatedLines {
+    /// Writes the summary of suppressed repetitions, if any.
+    fn write_summary<W>(&mut self, stream: &mut W) -> io::Result<()>
+    where
+        W: Write,
+    {
+        let count = mem::take(&mut self.repetitions);
+        if count != 0 {
+            let summary = StyledDisplay {
+                style: self.summary_style,
+                value: format_args!(
+                    "last message repeated {count} {}",
+                    if count == 1 { "time" } else { "times" }
+                ),
+            };
+            writeln!(stream, "{summary}")?;
+        }
+        Ok(())
+    }
+}
+
+impl<W> Finish<W> for RepeatedLines
+where
+    W: Write,
+{
+    /// Writes the summary of suppressed repetitions and the current line, if any.
+    fn finish(&mut self, stream: &mut W) -> io::Result<()> {
+        self.write_summary(stream)?;
+        if !self.line.is_empty() {
+            stream.write_all(&mem::take(&mut self.line))?;
+            self.previous = None;
+        }
+        stream.flush()
+    }
+}
+
+impl<W> Write for RepeatedLineSuppressor<W>
+where
+    W: Write,
+{
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let FinishOnDrop { stream, state } = &mut self.inner;
+        let mut rest = buf;
+        while let Some(pos) = rest.iter().position(|&byte| byte == b'\n') {
+            state.line.extend_from_slice(&rest[..pos]);
+            rest = &rest[pos + 1..];
+            let line = mem::take(&mut state.line);
+            if state.previous.as_ref() == Some(&line) {
+                state.repetitions += 1;
+            } else {
+                state.write_summary(stream)?;
+                stream.write_all(&line)?;
+                stream.write_all(b"\n")?;
+                state.previous = Some(line);
+            }
+        }
+        state.line.extend_from_slice(rest);
+        Ok(buf.len())
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.stream.flush()
+    }
+}
+
+/// Adapter that prefixes each line with its line number.
+///
+/// Each line is preceded by a gutter consisting of the line number, right-aligned to the
+/// [number width](Self::new) and displayed in the number style, and a space. Empty lines are
+/// preceded by the line number only. If [wrapping](Self::with_wrapping) is enabled, lines are
+/// wrapped to fit into the width together with the gutter, and continuation lines are either
+/// numbered as lines of their own or indented to align with the text of the first line.
+///
+/// If wrapping is enabled, lines are held back until they are terminated by a line feed.
+/// [`finish`](Self::finish) writes output that has been held back; this also happens when the
+/// adapter is dropped, ignoring errors.
+#[derive(Debug)]
+pub struct LineNumberingWriter<W>
+where
+    W: Write,
+{
+    /// The stream to which output is written, and the output that has been held back.
+    inner: FinishOnDrop<W, LineNumbering>,
+}
+
+/// State of a [`LineNumberingWriter`].
+#[derive(Debug)]
+struct LineNumbering {
+    /// The number of the next line.
+    next_number: usize,
+    /// The minimum number of columns occupied by a line number.
+    number_width: usize,
+    /// The style of the line numbers.
+    number_style: Style,
+    /// The maximum number of columns occupied by a line including the gutter, or `None` if lines
+    /// are not wrapped.
+    wrap_width: Option<usize>,
+    /// Whether continuation lines of wrapped lines are numbered rather than indented.
+    number_continuation_lines: bool,
+    /// Whether no byte of the current line has been written yet, if lines are not wrapped.
+    at_line_start: bool,
+    /// The bytes of the current line, which has not been terminated yet, if lines are wrapped.
+    line: Vec<u8>,
+}
+
+impl<W> LineNumberingWriter<W>
+where
+    W: Write,
+{
+    /// Creates an adapter that writes output to `stream`, with lines numbered from `start`.
+    #[inline]
+    #[must_use]
+    pub const fn new(stream: W, start: usize, number_width: usize, number_style: Style) -> Self {
+        Self {
+            inner: FinishOnDrop::new(
+                stream,
+                LineNumbering {
+                    next_number: start,
+                    number_width,
+                    number_style,
+                    wrap_width: None,
+                    number_continuation_lines: false,
+                    at_line_start: true,
+                    line: Vec::new(),
+                },
+            ),
+        }
+    }
+
+    /// Returns the adapter with wrapping enabled, so that no line exceeds `width` columns including
+    /// the gutter. Continuation lines are numbered if `number_continuation_lines` is `true`, and
+    /// indented otherwise.
+    #[inline]
+    #[must_use]
+    pub const fn with_wrapping(mut self, width: usize, number_continuation_lines: bool) -> Self {
+        self.inner.state.wrap_width = Some(width);
+        self.inner.state.number_continuation_lines = number_continuation_lines;
+        self
+    }
+
+    /// Returns the number of columns occupied by the gutter.
+    #[inline]
+    #[must_use]
+    pub const fn gutter_width(&self) -> usize {
+        self.inner.state.gutter_width()
+    }
+
+    /// Returns a reference to the stream.
+    #[inline]
+    #[must_use]
+    pub const fn get_ref(&self) -> &W {
+        &self.inner.stream
+    }
+
+    /// Returns a mutable reference to the stream.
+    #[inline]
+    #[must_use]
+    pub const fn get_mut(&mut self) -> &mut W {
+        &mut self.inner.stream
+    }
+
+    /// Writes output that has been held back and returns the stream.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing to or flushing the stream fails.
+    #[inline]
+    pub fn finish(self) -> io::Result<W> {
+        self.inner.into_stream()
+    }
+}
+
+impl LineNumbering {
+    /// Returns the number of columns occupied by the gutter.
+    #[inline]
+    #[must_use]
+    const fn gutter_width(&self) -> usize {
+        self.number_width + 1
+    }
+
+    /// Writes the line number of the next line, followed by a space if the line is not empty.
+    fn write_number<W>(&mut self, stream: &mut W, empty: bool) -> io::Result<()>
+    where
+        W: Write,
+    {
+        let number = StyledDisplay {
+            style: self.number_style,
+            value: format_args!("{:>1$}", self.next_number, self.number_width),
+        };
+        write!(stream, "{number}{}", if empty { "" } else { " " })?;
+        self.next_number += 1;
+        Ok(())
+    }
+
+    /// Writes the line, which does not contain line feeds, wrapped and with gutters, and followed
+    /// by a line feed if it is terminated.
+    fn write_wrapped<W>(
+        &mut self,
+        stream: &mut W,
+        line: &[u8],
+        width: usize,
+        terminated: bool,
+    ) -> io::Result<()>
+    where
+        W: Write,
+    {
+        let text = String::from_utf8_lossy(line);
+        let text_width = width.saturating_sub(self.gutter_width());
+        let lines = wrap_line(&text, text_width);
+        let last = lines.len() - 1;
+        for (index, wrapped) in lines.into_iter().enumerate() {
+            if index == 0 || self.number_continuation_lines {
+                self.write_number(stream, wrapped.is_empty())?;
+            } else {
+                write!(stream, "{:1$}", "", self.gutter_width())?;
+            }
+            stream.write_all(wrapped.as_bytes())?;
+            if index != last || terminated {
+                stream.write_all(b"\n")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<W> Finish<W> for LineNumbering
+where
+    W: Write,
+{
+    /// Writes the current line if lines are wrapped and it is not empty, and flushes the stream.
+    fn finish(&mut self, stream: &mut W) -> io::Result<()> {
+        if let Some(width) = self.wrap_width
+            && !self.line.is_empty()
+        {
+            let line = mem::take(&mut self.line);
+            self.write_wrapped(stream, &line, width, false)?;
+        }
+        stream.flush()
+    }
+}
+
+impl<W> Write for LineNumberingWriter<W>
+where
+    W: Write,
+{
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let FinishOnDrop { stream, state } = &mut self.inner;
+        let mut rest = buf;
+        if let Some(width) = state.wrap_width {
+            while let Some(pos) = rest.iter().position(|&byte| byte == b'\n') {
+                state.line.extend_from_slice(&rest[..pos]);
+                rest = &rest[pos + 1..];
+                let line = mem::take(&mut state.line);
+                state.write_wrapped(stream, &line, width, true)?;
+            }
+            state.line.extend_from_slice(rest);
+        } else {
+            while !rest.is_empty() {
+                let end = rest
+                    .iter()
+                    .position(|&byte| byte == b'\n')
+                    .map_or(rest.len(), |pos| pos + 1);
+                if state.at_line_start {
+                    state.write_number(stream, rest[0] == b'\n')?;
+                }
+                stream.write_all(&rest[..end])?;
+                state.at_line_start = rest[end - 1] == b'\n';
+                rest = &rest[end..];
+            }
+        }
+        Ok(buf.len())
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.stream.flush()
+    }
+}
+
+/// Adapter that truncates each line to a width instead of letting the terminal wrap it, similar to
+/// `less -S`.
+///
+/// Lines that do not fit are truncated at the end and marked by an ellipsis (`…`), with ANSI escape
+/// sequences retained as by [`truncate_styled`]. Lines are held back until they are terminated by
+/// a line feed, and invalid UTF-8 is replaced by U+FFFD. [`finish`](Self::finish) writes output
+/// that has been held back; this also happens when the adapter is dropped, ignoring errors.
+#[derive(Debug)]
+pub struct TruncatingWriter<W>
+where
+    W: Write,
+{
+    /// The stream to which output is written, and the output that has been held back.
+    inner: FinishOnDrop<W, Truncation>,
+}
+
+/// State of a [`TruncatingWriter`].
+#[derive(Debug)]
+struct Truncation {
+    /// The maximum number of columns occupied by a line.
+    width: usize,
+    /// The bytes of the current line, which has not been terminated yet.
+    line: Vec<u8>,
+}
+
+impl<W> TruncatingWriter<W>
+where
+    W: Write,
+{
+    /// Creates an adapter that writes output to `stream`, with each line truncated to `width`
+    /// columns.
+    #[inline]
+    #[must_use]
+    pub const fn new(stream: W, width: usize) -> Self {
+        Self {
+            inner: FinishOnDrop::new(
+                stream,
+                Truncation {
+                    width,
+                    line: Vec::new(),
+                },
+            ),
+        }
+    }
+
+    /// Returns a reference to the stream.
+    #[inline]
+    #[must_use]
+    pub const fn get_ref(&self) -> &W {
+        &self.inner.stream
+    }
+
+    /// Returns a mutable reference to the stream.
+    #[inline]
+    #[must_use]
+    pub const fn get_mut(&mut self) -> &mut W {
+        &mut self.inner.stream
+    }
+
+    /// Writes output that has been held back and returns the stream.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing to or flushing the stream fails.
+    #[inline]
+    pub fn finish(self) -> io::Result<W> {
+        self.inner.into_stream()
+    }
+}
+
+impl Truncation {
+    /// Writes the line, which does not contain line feeds, truncated.
+    fn write_truncated<W>(&self, stream: &mut W, line: &[u8]) -> io::Result<()>
+    where
+        W: Write,
+    {
+        let text = String::from_utf8_lossy(line);
+        stream.write_all(truncate_styled(&text, self.width).as_bytes())
+    }
+}
+
+impl<W> Finish<W> for Truncation
+where
+    W: Write,
+{
+    /// Writes the current line if it is not empty, and flushes the stream.
+    fn finish(&mut self, stream: &mut W) -> io::Result<()> {
+        if !self.line.is_empty() {
+            let line = mem::take(&mut self.line);
+            self.write_truncated(stream, &line)?;
+        }
+        stream.flush()
+    }
+}
+
+impl<W> Write for TruncatingWriter<W>
+where
+    W: Write,
+{
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let FinishOnDrop { stream, state } = &mut self.inner;
+        let mut rest = buf;
+        while let Some(pos) = rest.iter().position(|&byte| byte == b'\n') {
+            state.line.extend_from_slice(&rest[..pos]);
+            rest = &rest[pos + 1..];
+            let line = mem::take(&mut state.line);
+            state.write_truncated(stream, &line)?;
+            stream.write_all(b"\n")?;
+        }
+        state.line.extend_from_slice(rest);
+        Ok(buf.len())
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.stream.flush()
+    }
+}
+
+/// Guard that keeps a style set on a stream while it exists.
+///
+/// The style is set when the guard is created, and reset when it is dropped, including during
+/// unwinding after a panic or on an early return, so that an error does not leave the terminal in
+/// the style. Errors that occur while resetting the style on drop are ignored; use
+/// [`leave`](Self::leave) to handle them. Nothing is written if the style is the default style.
+///
+/// The guard implements [`Write`], so output can be written to the stream through it. Output that
+/// resets the style itself ends the effect of the guard before it is dropped.
+#[derive(Debug)]
+pub struct StyleScope<W>
+where
+    W: Write,
+{
+    /// The stream to which control sequences and output are written, and whether the style must be
+    /// reset.
+    inner: FinishOnDrop<W, ScopedStyle>,
+}
+
+/// State of a [`StyleScope`].
+#[derive(Debug)]
+struct ScopedStyle {
+    /// Whether the style has been set and must be reset.
+    active: bool,
+}
+
+impl<W> StyleScope<W>
+where
+    W: Write,
+{
+    /// Sets the style on `stream` and returns a guard that resets it when it is dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing to the stream fails.
+    pub fn enter(mut stream: W, style: Style) -> io::Result<Self> {
+        let mut buffer = Style::new_set_style_buffer();
+        let set_style = style.set_style(&mut buffer);
+        stream.write_all(set_style.as_bytes())?;
+        Ok(Self {
+            inner: FinishOnDrop::new(
+                stream,
+                ScopedStyle {
+                    active: !set_style.is_empty(),
+                },
+            ),
+        })
+    }
+
+    /// Returns a reference to the stream.
+    #[inline]
+    #[must_use]
+    pub const fn get_ref(&self) -> &W {
+        &self.inner.stream
+    }
+
+    /// Returns a mutable reference to the stream.
+    #[inline]
+    #[must_use]
+    pub const fn get_mut(&mut self) -> &mut W {
+        &mut self.inner.stream
+    }
+
+    /// Resets the style and returns the stream.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing to the stream fails.
+    #[inline]
+    pub fn leave(self) -> io::Result<W> {
+        self.inner.into_stream()
+    }
+}
+
+impl<W> Finish<W> for ScopedStyle
+where
+    W: Write,
+{
+    /// Resets the style if it has been set.
+    fn finish(&mut self, stream: &mut W) -> io::Result<()> {
+        if self.active {
+            self.active = false;
+            stream.write_all(RESET_STYLE.as_bytes())?;
+        }
+        Ok(())
+    }
+}
+
+impl<W> Write for StyleScope<W>
+where
+    W: Write,
+{
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.inner.stream.write(buf)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.stream.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Color;
+
+    use super::*;
+
+    /// Writes the chunks through a transcoding adapter and returns the output.
+    fn transcode(chunks: &[&[u8]], encoding: OutputEncoding) -> Vec<u8> {
+        let mut writer = TranscodingWriter::new(Vec::new(), encoding);
+        for chunk in chunks {
+            writer
+                .write_all(chunk)
+                .expect("writing to a vector succeeds");
+        }
+        writer.into_inner()
+    }
+
+    #[test]
+    fn transcode_utf8_passes_through() {
+        let result = transcode(&[b"gr\xc3\xbc\xff"], OutputEncoding::Utf8);
+        assert_eq!(result, b"gr\xc3\xbc\xff");
+    }
+
+    #[test]
+    fn transcode_latin1() {
+        let result = transcode(&["\x1b[1mgrün €\x1b[0m".as_bytes()], OutputEncoding::Latin1);
+        assert_eq!(result, b"\x1b[1mgr\xfcn ?\x1b[0m");
+    }
+
+    #[test]
+    fn transcode_ascii() {
+        let result = transcode(&["grün".as_bytes()], OutputEncoding::Ascii);
+        assert_eq!(result, b"gr?n");
+    }
+
+    #[test]
+    fn transcode_split_and_invalid() {
+        let result = transcode(
+            &[b"a\xc3", b"\xbcb\xff", b"\xe2\x82"],
+            OutputEncoding::Latin1,
+        );
+        assert_eq!(result, b"a\xfcb?");
+    }
+
+    /// Stream that accepts a limited number of bytes and then fails with a broken pipe error.
+    struct LimitedPipe {
+        /// The bytes written to the stream.
+        written: Vec<u8>,
+        /// The number of bytes the stream accepts.
+        capacity: usize,
+    }
+
+    impl Write for LimitedPipe {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            let len = buf.len().min(self.capacity - self.written.len());
+            if len == 0 && !buf.is_empty() {
+                return Err(io::ErrorKind::BrokenPipe.into());
+            }
+            self.written.extend_from_slice(&buf[..len]);
+            Ok(len)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            if self.written.len() == self.capacity {
+                Err(io::ErrorKind::BrokenPipe.into())
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    #[test]
+    fn broken_pipe_guard() {
+        let mut guard = BrokenPipeGuard::new(LimitedPipe {
+            written: Vec::new(),
+            capacity: 4,
+        });
+        guard
+            .write_all(b"foo\n")
+            .expect("the pipe accepts four bytes");
+        assert!(!guard.is_broken());
+        guard.write_all(b"bar\n").expect("a broken pipe is ignored");
+        guard.flush().expect("a broken pipe is ignored");
+        assert!(guard.is_broken());
+        assert_eq!(guard.into_inner().written, b"foo\n");
+    }
+
+    #[test]
+    fn broken_pipe_guard_other_errors() {
+        let mut guard = BrokenPipeGuard::new(io::Cursor::new([0; 2]));
+        let error = guard
+            .write_all(b"foo")
+            .expect_err("the cursor accepts two bytes");
+        assert_eq!(error.kind(), io::ErrorKind::WriteZero);
+        assert!(!guard.is_broken());
+    }
+
+    #[test]
+    fn column_tracking() {
+        let mut writer = ColumnTrackingWriter::new(Vec::new());
+        assert_eq!(writer.column(), 0);
+        writer
+            .write_all(b"foo")
+            .expect("writing to a vector succeeds");
+        assert_eq!(writer.column(), 3);
+        writer
+            .write_all("\x1b[1m日".as_bytes())
+            .expect("writing to a vector succeeds");
+        assert_eq!(writer.column(), 5);
+        writer
+            .write_all(b"\tx")
+            .expect("writing to a vector succeeds");
+        assert_eq!(writer.column(), 9);
+        writer
+            .write_all(b"\nab")
+            .expect("writing to a vector succeeds");
+        assert_eq!(writer.column(), 2);
+        writer
+            .write_all(b"cd\rx")
+            .expect("writing to a vector succeeds");
+        assert_eq!(writer.column(), 1);
+    }
+
+    #[test]
+    fn column_tracking_split_sequences() {
+        let mut writer = ColumnTrackingWriter::new(Vec::new());
+        writer
+            .write_all(b"a\x1b[3")
+            .expect("writing to a vector succeeds");
+        writer
+            .write_all(b"1mb\xe6\x97")
+            .expect("writing to a vector succeeds");
+        assert_eq!(writer.column(), 2);
+        writer
+            .write_all(b"\xa5")
+            .expect("writing to a vector succeeds");
+        assert_eq!(writer.column(), 4);
+        assert_eq!(writer.into_inner(), "a\x1b[31mb日".as_bytes());
+    }
+
+    #[test]
+    fn column_tracking_split_clusters() {
+        let mut writer = ColumnTrackingWriter::new(Vec::new());
+        writer
+            .write_all("x👨\u{200d}".as_bytes())
+            .expect("writing to a vector succeeds");
+        assert_eq!(writer.column(), 3);
+        writer
+            .write_all("👩\x1b]0;title".as_bytes())
+            .expect("writing to a vector succeeds");
+        assert_eq!(writer.column(), 3);
+        writer
+            .write_all(b"\x07\ty")
+            .expect("writing to a vector succeeds");
+        assert_eq!(writer.column(), 9);
+    }
+
+    #[test]
+    fn blank_line_collapsing() {
+        let mut writer = BlankLineCollapser::new(Vec::new(), 1);
+        for chunk in ["\n\nfoo\n", "\n  \n", "\t\n  bar\n\n", "\n"] {
+            writer
+                .write_all(chunk.as_bytes())
+                .expect("writing to a vector succeeds");
+        }
+        assert_eq!(writer.into_inner(), b"\nfoo\n\n  bar\n\n");
+    }
+
+    #[test]
+    fn blank_line_removal() {
+        let mut writer = BlankLineCollapser::new(Vec::new(), 0);
+        writer
+            .write_all(b"a\n\n \nb\n")
+            .expect("writing to a vector succeeds");
+        assert_eq!(writer.into_inner(), b"a\nb\n");
+    }
+
+    #[test]
+    fn repeated_line_suppression() {
+        let mut writer = RepeatedLineSuppressor::new(Vec::new(), Style::default());
+        for chunk in ["a\na\na", "\na\nb\n", "b\nc\na\n", "a\nd"] {
+            writer
+                .write_all(chunk.as_bytes())
+                .expect("writing to a vector succeeds");
+        }
+        let result = writer.finish().expect("writing to a vector succeeds");
+        assert_eq!(
+            String::from_utf8_lossy(&result),
+            "a\nlast message repeated 3 times\nb\nlast message repeated 1 time\nc\na\n\
+             last message repeated 1 time\nd"
+        );
+    }
+
+    #[test]
+    fn repeated_line_suppression_on_drop() {
+        let mut result = Vec::new();
+        let summary_style = Style {
+            bold: true,
+            ..Default::default()
+        };
+        let mut writer = RepeatedLineSuppressor::new(&mut result, summary_style);
+        writer
+            .write_all(b"x\nx\n")
+            .expect("writing to a vector succeeds");
+        drop(writer);
+        assert_eq!(result, b"x\n\x1b[1mlast message repeated 1 time\x1b[0m\n");
+    }
+
+    #[test]
+    fn line_numbering() {
+        let mut writer = LineNumberingWriter::new(Vec::new(), 9, 2, Style::default());
+        for chunk in ["foo\nb", "ar\n\nbaz"] {
+            writer
+                .write_all(chunk.as_bytes())
+                .expect("writing to a vector succeeds");
+        }
+        let result = writer.finish().expect("writing to a vector succeeds");
+        assert_eq!(
+            String::from_utf8_lossy(&result),
+            " 9 foo\n10 bar\n11\n12 baz"
+        );
+    }
+
+    #[test]
+    fn line_numbering_wrapped() {
+        let mut writer =
+            LineNumberingWriter::new(Vec::new(), 1, 1, Style::default()).with_wrapping(11, false);
+        writer
+            .write_all(b"the quick brown fox\n\njumps")
+            .expect("writing to a vector succeeds");
+        let result = writer.finish().expect("writing to a vector succeeds");
+        assert_eq!(
+            String::from_utf8_lossy(&result),
+            "1 the quick\n  brown fox\n2\n3 jumps"
+        );
+    }
+
+    #[test]
+    fn line_numbering_wrapped_numbered_and_styled() {
+        let mut result = Vec::new();
+        let number_style = Style {
+            bold: true,
+            ..Default::default()
+        };
+        let mut writer =
+            LineNumberingWriter::new(&mut result, 1, 1, number_style).with_wrapping(6, true);
+        writer
+            .write_all(b"ab cd\n")
+            .expect("writing to a vector succeeds");
+        drop(writer);
+        assert_eq!(result, b"\x1b[1m1\x1b[0m ab\n\x1b[1m2\x1b[0m cd\n");
+    }
+
+    #[test]
+    fn truncating_writer() {
+        let mut writer = TruncatingWriter::new(Vec::new(), 6);
+        for chunk in ["short\n\x1b[1mtoo long", " line\x1b[0m\n", "\nlast line"] {
+            writer
+                .write_all(chunk.as_bytes())
+                .expect("writing to a vector succeeds");
+        }
+        let result = writer.finish().expect("writing to a vector succeeds");
+        assert_eq!(
+            String::from_utf8_lossy(&result),
+            "short\n\x1b[1mtoo l…\x1b[0m\n\nlast …"
+        );
+    }
+
+    #[test]
+    fn style_scope_drop() {
+        let mut output = Vec::new();
+        {
+            let mut scope = StyleScope::enter(&mut output, Style::new().fg(Color::Red))
+                .expect("writing to a vector succeeds");
+            scope
+                .write_all(b"foo")
+                .expect("writing to a vector succeeds");
+        }
+        assert_eq!(output, b"\x1b[31mfoo\x1b[0m");
+    }
+
+    #[test]
+    fn style_scope_leave() {
+        let bold = StyleScope::enter(Vec::new(), Style::new().bold())
+            .expect("writing to a vector succeeds");
+        let output = bold.leave().expect("writing to a vector succeeds");
+        assert_eq!(output, b"\x1b[1m\x1b[0m");
+
+        let default =
+            StyleScope::enter(Vec::new(), Style::new()).expect("writing to a vector succeeds");
+        assert!(
+            default
+                .leave()
+                .expect("writing to a vector succeeds")
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn style_scope_drop_on_panic() {
+        let mut output = Vec::new();
+        let panicked = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            let _scope = StyleScope::enter(&mut output, Style::new().underlined())
+                .expect("writing to a vector succeeds");
+            panic!("rendering failed");
+        }));
+        assert!(panicked.is_err());
+        assert_eq!(output, b"\x1b[4m\x1b[0m");
+    }
+}